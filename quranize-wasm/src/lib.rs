@@ -1,6 +1,6 @@
 use std::iter::once;
 
-use quranize::Quranize;
+use quranize::{Explanation, Quranize};
 use serde_wasm_bindgen::{Error, to_value};
 use wasm_bindgen::prelude::*;
 
@@ -59,10 +59,10 @@ impl JsQuranize {
 
     fn encode(&self, text: &str) -> Vec<JsEncodeResult> {
         self.quranize
-            .encode(text)
+            .encode_with_explanations(text)
             .into_iter()
             .map(|(quran, location_count, explanations)| JsEncodeResult {
-                explanations: self.compress_explanation(&quran, explanations),
+                explanations: self.compress_explanation(explanations),
                 quran,
                 location_count,
             })
@@ -75,8 +75,7 @@ impl JsQuranize {
     }
 
     fn get_locations(&self, query: &str) -> Vec<JsLocation<'_>> {
-        let mut indexes = self.quranize.find(query);
-        indexes.sort_unstable();
+        let indexes = self.quranize.find_sorted(query);
         indexes
             .chunk_by(|(i, _), (next_i, _)| i == next_i)
             .map(|ijs| {
@@ -129,32 +128,20 @@ impl JsQuranize {
             .collect()
     }
 
-    fn compress_explanation(&self, quran: &str, explanations: Vec<&str>) -> Vec<JsExplanation> {
-        { quran.chars().zip(explanations) }
-            .fold(Vec::new(), |mut aqs: Vec<JsExplanation>, (q, e)| {
-                match (aqs.last_mut(), q) {
-                    (Some(laq), '\u{064B}'..='\u{0653}' | '\u{0670}') => {
-                        laq.alphabet.push_str(e);
-                        laq.quran.push(q);
-                    }
-                    _ => aqs.push(JsExplanation {
-                        alphabet: e.to_string(),
-                        quran: q.to_string(),
-                    }),
+    fn compress_explanation(&self, explanations: Vec<Explanation>) -> Vec<JsExplanation> {
+        explanations.into_iter().fold(Vec::new(), |mut aqs, e| {
+            match aqs.last_mut() {
+                Some(laq) if laq.alphabet.is_empty() => {
+                    laq.alphabet += &e.alphabet;
+                    laq.quran += &e.quran;
                 }
-                aqs
-            })
-            .into_iter()
-            .fold(Vec::new(), |mut aqs, eq| {
-                match aqs.last_mut() {
-                    Some(laq) if laq.alphabet.is_empty() => {
-                        laq.alphabet += &eq.alphabet;
-                        laq.quran += &eq.quran;
-                    }
-                    _ => aqs.push(eq),
-                }
-                aqs
-            })
+                _ => aqs.push(JsExplanation {
+                    alphabet: e.alphabet,
+                    quran: e.quran,
+                }),
+            }
+            aqs
+        })
     }
 }
 
@@ -201,10 +188,43 @@ mod tests {
     #[test]
     fn test_compress_explanation() {
         let q = JsQuranize::new();
-        let quran = "بِرَبِّ النّاسِ";
-        let expl = "b-i-r-o-b-b-i----n-n-a-s-".split('-').collect();
+        let (_, _, expl) = q.quranize.encode_with_explanations("birobbinnas").remove(0);
+        assert_eq!(
+            q.compress_explanation(expl)
+                .into_iter()
+                .map(|aq| (aq.alphabet, aq.quran))
+                .collect::<Vec<_>>(),
+            [
+                ("bi", "بِ"),
+                ("ro", "رَ"),
+                ("bbi", "بِّ"),
+                ("nna", " النَّ"),
+                ("s", "اس"),
+            ]
+            .map(|(x, y)| (x.to_string(), y.to_string()))
+        );
+
+        let groups = [
+            ("bi", "بِ"),
+            ("ro", "رَ"),
+            ("bbi", "بِّ"),
+            ("", " "),
+            ("", "ا"),
+            ("", "ل"),
+            ("nn", "نّ"),
+            ("a", "ا"),
+            ("s", "سِ"),
+        ];
+        let expl = Vec::from_iter(groups.map(|(alphabet, quran)| Explanation {
+            alphabet: alphabet.to_string(),
+            quran: quran.to_string(),
+            input: 0..0,
+            source: 0..0,
+            quran_range: 0..0,
+            contextual: false,
+        }));
         assert_eq!(
-            q.compress_explanation(quran, expl)
+            q.compress_explanation(expl)
                 .into_iter()
                 .map(|aq| (aq.alphabet, aq.quran))
                 .collect::<Vec<_>>(),
@@ -0,0 +1,140 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use crate::{Quranize, suffix_tree::is_separator, transliteration::strip_harakat};
+
+/// Form of the words counted by [`Quranize::ngram_stats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordForm {
+    /// Words as written, with their harakat, e.g. `"الرَّحْمَٰنِ"`.
+    Diacritised,
+    /// Words with their harakat stripped, e.g. `"الرحمن"`.
+    Skeleton,
+}
+
+/// Grouping of the ayas counted by [`Quranize::ngram_stats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    /// One group for the whole Quran, numbered `0`.
+    Quran,
+    /// One group for each sura, numbered `1..=114`.
+    Sura,
+    /// One group for each juz, numbered `1..=30`.
+    Juz,
+}
+
+/// Frequencies of words, or n-grams of words, in a group of ayas.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NgramStats {
+    /// Occurrence count of each n-gram, whose words are joined by a single space.
+    pub frequencies: BTreeMap<String, usize>,
+}
+
+impl NgramStats {
+    /// Count all n-gram occurrences.
+    pub fn total_count(&self) -> usize {
+        self.frequencies.values().sum()
+    }
+
+    /// Count distinct n-grams.
+    pub fn distinct_count(&self) -> usize {
+        self.frequencies.len()
+    }
+
+    /// List n-grams occurring only once (hapax legomena).
+    pub fn hapax_legomena(&self) -> Vec<&str> {
+        { self.frequencies.iter() }
+            .filter_map(|(k, &n)| (n == 1).then_some(k.as_str()))
+            .collect()
+    }
+
+    /// List the `k` most frequent n-grams, from the most frequent one.
+    pub fn most_frequent(&self, k: usize) -> Vec<(&str, usize)> {
+        let mut frequencies =
+            Vec::from_iter(self.frequencies.iter().map(|(k, &n)| (k.as_str(), n)));
+        frequencies.sort_by_key(|&(k, n)| (Reverse(n), k));
+        frequencies.truncate(k);
+        frequencies
+    }
+}
+
+impl Quranize {
+    /// Count n-grams of `n` consecutive words within each aya, grouped by `grouping`.
+    /// Words are separated by spaces and pause marks, so n-grams may span a pause mark,
+    /// but never two ayas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{Grouping, WordForm};
+    ///
+    /// let q = quranize::Quranize::new();
+    /// let stats = q.ngram_stats(2, WordForm::Skeleton, Grouping::Sura);
+    /// assert_eq!(stats[&1].frequencies["الرحمن الرحيم"], 2);
+    /// let stats = &q.ngram_stats(1, WordForm::Skeleton, Grouping::Quran)[&0];
+    /// assert_eq!(stats.most_frequent(2), [("من", 2763), ("الله", 2153)]);
+    /// ```
+    pub fn ngram_stats(
+        &self,
+        n: usize,
+        form: WordForm,
+        grouping: Grouping,
+    ) -> BTreeMap<u8, NgramStats> {
+        let mut stats = BTreeMap::<u8, NgramStats>::new();
+        for (i, &(_, sura, _, text)) in self.data.iter().enumerate() {
+            let group = match grouping {
+                Grouping::Quran => 0,
+                Grouping::Sura => sura,
+                Grouping::Juz => self.get_juz(i).unwrap_or_default(),
+            };
+            let frequencies = &mut stats.entry(group).or_default().frequencies;
+            let words = Vec::from_iter(text.split(is_separator).filter(|w| !w.is_empty()));
+            for ngram in words.windows(n.max(1)) {
+                let ngram = ngram.join(" ");
+                let ngram = match form {
+                    WordForm::Diacritised => ngram,
+                    WordForm::Skeleton => strip_harakat(&ngram),
+                };
+                *frequencies.entry(ngram).or_default() += 1;
+            }
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ngram_stats() {
+        let q = Quranize::new();
+        let words = &q.ngram_stats(1, WordForm::Diacritised, Grouping::Quran)[&0];
+        let skeletons = &q.ngram_stats(1, WordForm::Skeleton, Grouping::Quran)[&0];
+        assert_eq!(words.total_count(), skeletons.total_count());
+        assert!(words.distinct_count() > skeletons.distinct_count());
+        assert!(words.hapax_legomena().contains(&"مُدْهَامَّتَانِ"));
+        let allah_count = { words.frequencies.iter() }
+            .filter_map(|(w, &n)| (strip_harakat(w) == "الله").then_some(n))
+            .sum::<usize>();
+        assert_eq!(skeletons.frequencies["الله"], allah_count);
+
+        let suras = q.ngram_stats(1, WordForm::Diacritised, Grouping::Sura);
+        let juzs = q.ngram_stats(1, WordForm::Diacritised, Grouping::Juz);
+        assert_eq!(suras.len(), 114);
+        assert_eq!(juzs.len(), 30);
+        assert_eq!(
+            suras.values().map(NgramStats::total_count).sum::<usize>(),
+            words.total_count()
+        );
+        assert_eq!(
+            juzs.values().map(NgramStats::total_count).sum::<usize>(),
+            words.total_count()
+        );
+        assert_eq!(suras[&112].total_count(), 15);
+
+        let bigrams = &q.ngram_stats(2, WordForm::Diacritised, Grouping::Quran)[&0];
+        assert_eq!(bigrams.total_count(), words.total_count() - q.data.len());
+        assert_eq!(bigrams.most_frequent(1), [("إِنَّ اللَّهَ", 205)]);
+    }
+}
@@ -0,0 +1,271 @@
+use std::{collections::BTreeMap, fs, io, ops::Range, path::Path};
+
+use serde_json::Value;
+
+use crate::{Quranize, WordLocation, suffix_tree::Index, words::aya_row};
+
+/// Recitation timestamps of one reciter, in milliseconds from the start of the recording,
+/// mapped to aya rows and [`WordLocation`]s.
+///
+/// This type requires the `audio` feature.
+///
+/// # Examples
+///
+/// ```
+/// use quranize::{AudioTiming, Quranize};
+///
+/// let timing = AudioTiming::parse_csv("sura,aya,word,start,end
+/// 1,1,1,0,600
+/// 1,1,2,600,1200
+/// 1,1,3,1250,2300
+/// 1,1,4,2300,3900").unwrap();
+/// assert_eq!(timing.word_at(700), Some((1, 1, 2)));
+/// assert_eq!(timing.word_at(1220), None);
+/// assert_eq!(timing.aya_at(1220), Some(0));
+///
+/// let q = Quranize::new();
+/// let hit = q.find_sorted("الرَّحْمَٰنِ الرَّحِيمِ")[0];
+/// assert_eq!(q.get_audio_start(&timing, hit), Some(1250));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AudioTiming {
+    ayas: Vec<(usize, Range<u64>)>,
+    words: Vec<(WordLocation, Range<u64>)>,
+}
+
+impl AudioTiming {
+    /// Load timestamps from a file on local disk, parsed with [`parse_json`](Self::parse_json)
+    /// if its extension is `json`, or with [`parse_csv`](Self::parse_csv) otherwise.
+    /// Malformed content gives an error of kind [`io::ErrorKind::InvalidData`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        match path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("json"))
+        {
+            true => Self::parse_json(&text).map_err(|e| invalid(e.to_string())),
+            false => Self::parse_csv(&text).map_err(|n| invalid(format!("malformed line {n}"))),
+        }
+    }
+
+    /// Parse timestamps from comma separated lines of `sura,aya,start,end` for ayas, or
+    /// `sura,aya,word,start,end` for words. A header line, empty lines, and comment lines
+    /// starting with `#` are skipped. Aya timestamps missing from the lines are taken from
+    /// the first and the last word of the aya. Words are numbered as in [`WordLocation`].
+    /// Returns the line number (starting from 1) of the first malformed line as an error.
+    pub fn parse_csv(text: &str) -> Result<Self, usize> {
+        let mut timing = Self::default();
+        for (n, line) in text.lines().enumerate() {
+            let fields = Vec::from_iter(line.split(',').map(str::trim));
+            let is_header = n == 0 && fields[0].parse::<u64>().is_err();
+            if is_header || line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let numbers = Vec::from_iter(fields.iter().map_while(|f| f.parse::<u64>().ok()));
+            let added = match (&numbers[..], fields.len()) {
+                (&[s, a, start, end], 4) => timing.add_aya(s, a, start..end),
+                (&[s, a, w, start, end], 5) => timing.add_word(s, a, w, start..end),
+                _ => None,
+            };
+            added.ok_or(n + 1)?;
+        }
+        timing.finish();
+        Ok(timing)
+    }
+
+    /// Parse timestamps from JSON in the format of verse timings of common recitation APIs, i.e.
+    /// objects with a `"verse_key"` (`"sura:aya"`), `"timestamp_from"` and `"timestamp_to"`, and
+    /// optionally `"segments"`, a list of `[word, start, end]`. The objects may be nested
+    /// anywhere in the JSON, e.g. in a top level list or under `"audio_files"`.
+    /// Invalid objects are skipped.
+    pub fn parse_json(text: &str) -> Result<Self, serde_json::Error> {
+        let mut timing = Self::default();
+        let mut stack = vec![serde_json::from_str::<Value>(text)?];
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Object(_) if value.get("verse_key").is_some() => {
+                    timing.add_verse_timing(&value);
+                }
+                Value::Object(object) => stack.extend(object.into_iter().map(|(_, v)| v)),
+                Value::Array(values) => stack.extend(values),
+                _ => {}
+            }
+        }
+        timing.finish();
+        Ok(timing)
+    }
+
+    fn add_verse_timing(&mut self, verse: &Value) -> Option<()> {
+        let (s, a) = verse["verse_key"].as_str()?.split_once(':')?;
+        let (s, a) = (s.parse().ok()?, a.parse().ok()?);
+        let (start, end) = (
+            verse["timestamp_from"].as_u64(),
+            verse["timestamp_to"].as_u64(),
+        );
+        if let (Some(start), Some(end)) = (start, end) {
+            self.add_aya(s, a, start..end)?;
+        }
+        for segment in verse["segments"].as_array().into_iter().flatten() {
+            let numbers = Vec::from_iter(segment.as_array()?.iter().filter_map(Value::as_u64));
+            if let [.., w, start, end] = numbers[..] {
+                self.add_word(s, a, w, start..end)?;
+            }
+        }
+        Some(())
+    }
+
+    fn add_aya(&mut self, sura: u64, aya: u64, time: Range<u64>) -> Option<()> {
+        let i = aya_row(sura.try_into().ok()?, aya.try_into().ok()?)?;
+        self.ayas.push((i, time));
+        Some(())
+    }
+
+    fn add_word(&mut self, sura: u64, aya: u64, word: u64, time: Range<u64>) -> Option<()> {
+        let location = (
+            sura.try_into().ok()?,
+            aya.try_into().ok()?,
+            word.try_into().ok()?,
+        );
+        aya_row(location.0, location.1).filter(|_| location.2 > 0)?;
+        self.words.push((location, time));
+        Some(())
+    }
+
+    fn finish(&mut self) {
+        let mut word_spans = BTreeMap::<usize, Range<u64>>::new();
+        for ((s, a, _), time) in &self.words {
+            let span = word_spans.entry(aya_row(*s, *a).unwrap());
+            let span = span.or_insert(time.clone());
+            (span.start, span.end) = (span.start.min(time.start), span.end.max(time.end));
+        }
+        for (i, span) in word_spans {
+            if !self.ayas.iter().any(|(j, _)| *j == i) {
+                self.ayas.push((i, span));
+            }
+        }
+        self.ayas.sort_by_key(|(i, t)| (t.start, *i));
+        self.words.sort_by_key(|(l, t)| (t.start, *l));
+    }
+
+    /// Get the aya row (`0..6236`) being recited at time `t`.
+    pub fn aya_at(&self, t: u64) -> Option<usize> {
+        at(&self.ayas, t)
+    }
+
+    /// Get the location of the word being recited at time `t`.
+    pub fn word_at(&self, t: u64) -> Option<WordLocation> {
+        at(&self.words, t)
+    }
+
+    /// Get the time range of aya row `i`.
+    pub fn aya_time(&self, i: usize) -> Option<Range<u64>> {
+        { self.ayas.iter() }
+            .find(|(j, _)| *j == i)
+            .map(|(_, t)| t.clone())
+    }
+
+    /// Get the time range of the word at `location`.
+    pub fn word_time(&self, location: WordLocation) -> Option<Range<u64>> {
+        { self.words.iter() }
+            .find(|(l, _)| *l == location)
+            .map(|(_, t)| t.clone())
+    }
+}
+
+fn at<T: Copy>(times: &[(T, Range<u64>)], t: u64) -> Option<T> {
+    let k = times.partition_point(|(_, time)| time.start <= t);
+    let (x, time) = times.get(k.checked_sub(1)?)?;
+    time.contains(&t).then_some(*x)
+}
+
+impl Quranize {
+    /// Get the time in `timing` where location `(i, j)`, e.g. from [`find`](Self::find), starts to
+    /// be recited, i.e. the start of its word, or of its aya if `timing` has no word timestamps
+    /// for it.
+    ///
+    /// This method requires the `audio` feature.
+    pub fn get_audio_start(&self, timing: &AudioTiming, (i, j): Index) -> Option<u64> {
+        let word = self
+            .get_word_location((i, j))
+            .and_then(|l| timing.word_time(l));
+        word.or_else(|| timing.aya_time(i)).map(|t| t.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_csv() {
+        let timing = AudioTiming::parse_csv("1,2,5000,9000\n\n# words\n1,2,1,5000,6000").unwrap();
+        assert_eq!(timing.aya_time(1), Some(5000..9000));
+        assert_eq!(timing.word_time((1, 2, 1)), Some(5000..6000));
+        assert_eq!(timing.aya_at(8999), Some(1));
+        assert_eq!(timing.aya_at(9000), None);
+        assert_eq!(timing.word_at(6000), None);
+
+        let timing = AudioTiming::parse_csv("1,3,1,100,200\n1,3,2,200,300").unwrap();
+        assert_eq!(timing.aya_time(2), Some(100..300));
+        assert_eq!(timing.aya_at(50), None);
+
+        assert_eq!(AudioTiming::parse_csv("1,1,0,10\n1,8,0,10"), Err(2));
+        assert_eq!(AudioTiming::parse_csv("1,1,0,10\n1,1,x,0,10"), Err(2));
+        assert_eq!(AudioTiming::parse_csv("1,1,0,0,10"), Err(1));
+        assert_eq!(AudioTiming::parse_csv(""), Ok(AudioTiming::default()));
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"{"audio_files": [{"verse_timings": [
+            {"verse_key": "2:2", "timestamp_from": 7000, "timestamp_to": 15000,
+             "segments": [[1, 7000, 7800], [2, 7800, 9000], [3, 9100, 9500], [4, 9500, 10200]]},
+            {"verse_key": "2:1", "timestamp_from": 0, "timestamp_to": 7000, "segments": []},
+            {"verse_key": "200:1", "timestamp_from": 0, "timestamp_to": 7000}
+        ]}]}"#;
+        let timing = AudioTiming::parse_json(json).unwrap();
+        assert_eq!(timing.aya_at(6999), Some(7));
+        assert_eq!(timing.aya_at(7000), Some(8));
+        assert_eq!(timing.word_at(9200), Some((2, 2, 3)));
+        assert_eq!(timing.word_at(9050), None);
+
+        let q = Quranize::new();
+        let hit = q.find_sorted("لَا رَيْبَ")[0];
+        assert_eq!(q.get_audio_start(&timing, hit), Some(9100));
+        let hit = q.find_sorted("هُدًى لِّلْمُتَّقِينَ")[0];
+        assert_eq!(q.get_audio_start(&timing, hit), Some(7000));
+        assert_eq!(q.get_audio_start(&timing, (0, 0)), None);
+
+        let timing = AudioTiming::parse_csv("2,21,1,400,1300\n2,21,2,1300,1900").unwrap();
+        let hit = q.find_sorted("أَيُّهَا النَّاسُ اعْبُدُوا")[0];
+        assert_eq!(q.get_audio_start(&timing, hit), Some(400));
+
+        assert!(AudioTiming::parse_json("{").is_err());
+        assert_eq!(
+            AudioTiming::parse_json("[]").unwrap(),
+            AudioTiming::default()
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("quranize-test-audio-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let csv = dir.join("timing.csv");
+        fs::write(&csv, "1,1,0,10\n").unwrap();
+        assert_eq!(AudioTiming::load(&csv).unwrap().aya_at(5), Some(0));
+        let json = dir.join("timing.json");
+        fs::write(&json, "not json").unwrap();
+        let error = AudioTiming::load(&json).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let missing = dir.join("missing.csv");
+        assert_eq!(
+            AudioTiming::load(missing).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    cell::Cell,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+/// Limits for [`Quranize::encode_with_budget`](crate::Quranize::encode_with_budget).
+/// Every limit is optional, and the search stops as soon as any of them is reached.
+#[derive(Clone, Debug, Default)]
+pub struct EncodeBudget {
+    /// Maximum number of search states (tree edges tried against the input) to visit.
+    pub max_visited_states: Option<usize>,
+    /// Maximum number of results to collect.
+    pub max_results: Option<usize>,
+    /// Point in time after which the search stops.
+    pub deadline: Option<Instant>,
+}
+
+/// Shared flag for cancelling a running search from another thread.
+/// Clones share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new, not yet cancelled, token.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Request every search observing this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct Tracker<'a> {
+    budget: Option<&'a EncodeBudget>,
+    token: Option<&'a CancellationToken>,
+    visited_states: Cell<usize>,
+    results: Cell<usize>,
+    truncated: Cell<bool>,
+}
+
+impl<'a> Tracker<'a> {
+    const DEADLINE_CHECK_INTERVAL: usize = 64;
+
+    pub(crate) fn new(
+        budget: Option<&'a EncodeBudget>,
+        token: Option<&'a CancellationToken>,
+    ) -> Self {
+        Self {
+            budget,
+            token,
+            visited_states: Cell::new(0),
+            results: Cell::new(0),
+            truncated: Cell::new(false),
+        }
+    }
+
+    pub(crate) fn unlimited() -> Self {
+        Self::new(None, None)
+    }
+
+    pub(crate) fn visit(&self) -> bool {
+        if self.truncated.get() {
+            return false;
+        }
+        let n = self.visited_states.get() + 1;
+        self.visited_states.set(n);
+        let exhausted = self.token.is_some_and(|t| t.is_cancelled())
+            || self.budget.is_some_and(|b| {
+                b.max_visited_states.is_some_and(|m| n > m)
+                    || b.max_results.is_some_and(|m| self.results.get() >= m)
+                    || (n % Self::DEADLINE_CHECK_INTERVAL == 1
+                        && b.deadline.is_some_and(|d| Instant::now() >= d))
+            });
+        self.truncated.set(exhausted);
+        !exhausted
+    }
+
+    pub(crate) fn found(&self) -> bool {
+        let max_results = self.budget.and_then(|b| b.max_results);
+        let full = max_results.is_some_and(|m| self.results.get() >= m);
+        match full {
+            true => self.truncated.set(true),
+            false => self.results.set(self.results.get() + 1),
+        }
+        !full
+    }
+
+    pub(crate) fn truncated(&self) -> bool {
+        self.truncated.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_tracker() {
+        let t = Tracker::unlimited();
+        assert!((0..1000).all(|_| t.visit() && t.found()));
+        assert!(!t.truncated());
+
+        let budget = EncodeBudget {
+            max_visited_states: Some(3),
+            ..Default::default()
+        };
+        let t = Tracker::new(Some(&budget), None);
+        assert!(t.visit() && t.visit() && t.visit());
+        assert!(!t.visit());
+        assert!(t.truncated());
+
+        let budget = EncodeBudget {
+            max_results: Some(1),
+            ..Default::default()
+        };
+        let t = Tracker::new(Some(&budget), None);
+        assert!(t.visit() && t.found());
+        assert!(!t.found());
+        assert!(!t.visit());
+        assert!(t.truncated());
+
+        let budget = EncodeBudget {
+            deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(!Tracker::new(Some(&budget), None).visit());
+
+        let token = CancellationToken::new();
+        let t = Tracker::new(None, Some(&token));
+        assert!(t.visit());
+        token.clone().cancel();
+        assert!(token.is_cancelled());
+        assert!(!t.visit());
+        assert!(t.truncated());
+    }
+}
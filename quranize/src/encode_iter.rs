@@ -0,0 +1,224 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use crate::{
+    NormalizedKey, Quranize, RawEncodeResult, SearchScope, Tsl,
+    budget::Tracker,
+    suffix_tree::{Edge, Index},
+    transliteration::Reading,
+};
+
+/// Char `c` of quran text read as `tsl`, waiting to be followed by the walk of [`EncodeIter`].
+struct Frame {
+    /// Number of chars read before `c`, and their length in bytes.
+    depth: (usize, usize),
+    c: char,
+    tsl: Tsl,
+    /// Position in the input right after `tsl`.
+    pos: usize,
+    /// Edge of `c`, with the rest of the edge after `c`.
+    edge: Edge<'static>,
+    /// Reading of the char after `c`.
+    reading: Reading,
+}
+
+/// Walk of the suffix tree with an explicit stack, reading the input into quran forms one
+/// result at a time. Every encode method collects its results from this walk.
+pub(crate) struct EncodeIter<'a> {
+    quranize: &'a Quranize,
+    key: NormalizedKey,
+    tracker: Tracker<'a>,
+    scope: Cow<'a, SearchScope>,
+    stack: Vec<Frame>,
+    quran: String,
+    tsls: Vec<Tsl>,
+    seen: HashSet<String>,
+}
+
+impl<'a> EncodeIter<'a> {
+    /// Start walking with each of `readings`, where [`Reading::Muqottoah`] reads the second
+    /// input of `key` and the others read the first one.
+    pub(crate) fn new(
+        quranize: &'a Quranize,
+        key: NormalizedKey,
+        readings: &[Reading],
+        tracker: Tracker<'a>,
+        scope: Cow<'a, SearchScope>,
+    ) -> Self {
+        let mut iter = Self {
+            quranize,
+            key,
+            tracker,
+            scope,
+            stack: vec![],
+            quran: String::new(),
+            tsls: vec![],
+            seen: HashSet::new(),
+        };
+        for &reading in readings.iter().rev() {
+            if !iter.input(reading).is_empty() {
+                for &edge in quranize.tree.edges_from(0).rev() {
+                    iter.push_frames((0, 0), 0, edge, None, reading);
+                }
+            }
+        }
+        iter
+    }
+
+    fn input(&self, reading: Reading) -> &str {
+        match reading {
+            Reading::Muqottoah => &self.key.1,
+            _ => &self.key.0,
+        }
+    }
+
+    /// Check whether the walk was stopped by the tracker before exploring every possibility.
+    pub(crate) fn truncated(&self) -> bool {
+        self.tracker.truncated()
+    }
+
+    /// Push frames reading the first char of edge `(v, w, l)` after char `context`, matching the
+    /// input from `pos`, unless the edge is out of scope or the tracker stops the walk.
+    fn push_frames(
+        &mut self,
+        depth: (usize, usize),
+        pos: usize,
+        (v, w, l): Edge<'static>,
+        context: Option<char>,
+        reading: Reading,
+    ) {
+        let in_scope = self.scope.intersects(&self.quranize.tree.vertices[w].3);
+        let Some(c) = l
+            .chars()
+            .next()
+            .filter(|_| in_scope && self.tracker.visit())
+        else {
+            return;
+        };
+        let s = match reading {
+            Reading::Muqottoah => &self.key.1[pos..],
+            _ => &self.key.0[pos..],
+        };
+        let start = self.stack.len();
+        let tsls = reading
+            .tsls(context, c)
+            .filter(|(tsl, _, _)| s.starts_with(tsl));
+        self.stack.extend(tsls.map(|(tsl, ctx, next)| Frame {
+            depth,
+            c,
+            tsl: (tsl, ctx),
+            pos: pos + tsl.len(),
+            edge: (v, w, &l[c.len_utf8()..]),
+            reading: next,
+        }));
+        self.stack[start..].reverse();
+    }
+}
+
+impl Iterator for EncodeIter<'_> {
+    type Item = RawEncodeResult;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(frame) = self.stack.pop() {
+            let Frame {
+                depth: (n, len),
+                c,
+                tsl,
+                pos,
+                edge: (v, w, l),
+                reading,
+            } = frame;
+            self.tsls.truncate(n);
+            self.quran.truncate(len);
+            self.tsls.push(tsl);
+            self.quran.push(c);
+            let depth = (n + 1, self.quran.len());
+            match (pos == self.input(reading).len(), l) {
+                (true, _) => {
+                    let count = self.quranize.count_end(w, l, reading, &self.scope);
+                    if count > 0 && !self.seen.contains(&self.quran) && self.tracker.found() {
+                        self.seen.insert(self.quran.clone());
+                        let muq = reading == Reading::Muqottoah;
+                        return Some((self.quran.clone(), (w, count), self.tsls.clone(), muq));
+                    }
+                }
+                (false, "") => {
+                    for &edge in self.quranize.tree.edges_from(w).rev() {
+                        self.push_frames(depth, pos, edge, Some(c), reading);
+                    }
+                }
+                (false, l) => self.push_frames(depth, pos, (v, w, l), Some(c), reading),
+            }
+        }
+        None
+    }
+}
+
+impl Quranize {
+    /// Do transliteration on `s` like [`encode`](Self::encode), but lazily, walking the suffix
+    /// tree only as far as needed to produce the next result.
+    ///
+    /// Results are the same as [`encode`](Self::encode), except that they come in traversal order
+    /// instead of being sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let first = q.encode_iter("bismillah").next();
+    /// assert_eq!(first.map(|(quran, _, _)| quran), Some("بِسْمِ اللَّه".to_string()));
+    /// ```
+    pub fn encode_iter(&self, s: &str) -> impl Iterator<Item = (String, usize, Vec<&'static str>)> {
+        let (key, readings) = (Self::normalize_key(s), [Reading::Plain, Reading::Muqottoah]);
+        let scope = Cow::Owned(SearchScope::all());
+        { EncodeIter::new(self, key, &readings, Tracker::unlimited(), scope) }
+            .map(|(q, (_, n), e, _)| (q, n, Self::strip_context(e)))
+    }
+
+    /// Find `s` in Alquran like [`find`](Self::find), but lazily, so taking only the first few
+    /// locations does not enumerate all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let first_ten = Vec::from_iter(q.find_iter("مِنْ").take(10));
+    /// assert_eq!(first_ten, q.find("مِنْ")[..10]);
+    /// ```
+    pub fn find_iter(&self, s: &str) -> impl Iterator<Item = Index> {
+        self.tree.find_iter(s, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_iter() {
+        let q = Quranize::new();
+        for s in ["مِنْ", "اللَّهِ", "بِسْمِ", "الم", "", "abc"] {
+            assert_eq!(Vec::from_iter(q.find_iter(s)), q.find(s));
+        }
+    }
+
+    #[test]
+    fn test_encode_iter() {
+        let q = Quranize::new();
+        let texts = [
+            "bismillah",
+            "alif lam mim",
+            "robbil alamin",
+            "qul",
+            "kaf ha ya 'ain shod",
+            "wa'tasimu bihablillah",
+            "",
+            "xyz",
+        ];
+        for text in texts {
+            let mut results = Vec::from_iter(q.encode_iter(text));
+            results.sort_unstable_by(|x, y| x.0.cmp(&y.0));
+            assert_eq!(results, q.encode(text), "text = {text}");
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::ops::Range;
+
+use crate::transliteration::is_harakat;
+
+/// Explanation of one group of an encode result: a base char with its following harakat,
+/// and the part of the input transliterated into it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// Alphabetic text consumed by this group, e.g. `"bi"`.
+    pub alphabet: String,
+    /// Quran text of this group, e.g. `"بِ"`.
+    pub quran: String,
+    /// Byte range of `alphabet` in the normalized input.
+    pub input: Range<usize>,
+    /// Byte range in the original input text, before normalization, transliterated into this
+    /// group. Chars dropped by normalization (spaces, punctuation) are only included when they
+    /// sit between chars of this group.
+    pub source: Range<usize>,
+    /// Byte range of `quran` in the quran form of the encode result.
+    pub quran_range: Range<usize>,
+    /// Whether any char of this group was mapped by a contextual rule, i.e. its transliteration
+    /// depends on the previous char (e.g. the silent `ل` in `"بِاللَّهِ"` read as `"billah"`).
+    pub contextual: bool,
+}
+
+pub(crate) fn explain(
+    quran: &str,
+    tsls: &[(&'static str, bool)],
+    sources: &[Range<usize>],
+) -> Vec<Explanation> {
+    let mut start = 0;
+    let groups_iter = quran.char_indices().zip(tsls);
+    let mut groups = groups_iter.fold(
+        Vec::<Explanation>::new(),
+        |mut groups, ((j, q), &(tsl, contextual))| {
+            let input = start..start + tsl.len();
+            let quran_range = j..j + q.len_utf8();
+            start = input.end;
+            match groups.last_mut() {
+                Some(g) if is_harakat(q) => {
+                    g.alphabet.push_str(tsl);
+                    g.quran.push(q);
+                    g.input.end = input.end;
+                    g.quran_range.end = quran_range.end;
+                    g.contextual |= contextual;
+                }
+                _ => groups.push(Explanation {
+                    alphabet: tsl.to_string(),
+                    quran: q.to_string(),
+                    input,
+                    source: 0..0,
+                    quran_range,
+                    contextual,
+                }),
+            }
+            groups
+        },
+    );
+    groups
+        .iter_mut()
+        .for_each(|g| g.source = source_range(&g.input, sources));
+    groups
+}
+
+fn source_range(input: &Range<usize>, sources: &[Range<usize>]) -> Range<usize> {
+    match (input.start.checked_sub(1), input.is_empty()) {
+        (_, false) => sources[input.start].start..sources[input.end - 1].end,
+        (Some(i), true) => sources[i].end..sources[i].end,
+        (None, true) => { sources.first() }.map_or(0..0, |r| r.start..r.start),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_explain() {
+        let quran = "بِاللَّهِ";
+        let tsls = [
+            ("b", false),
+            ("i", false),
+            ("", false),
+            ("", true),
+            ("", false),
+        ];
+        let tsls = tsls
+            .into_iter()
+            .chain([("l", true), ("a", false), ("h", false), ("i", false)]);
+        let sources = [0..1, 1..2, 3..4, 4..5, 5..6, 6..7]; // "Bi laHi"
+        let groups = Vec::from_iter(
+            explain(quran, &Vec::from_iter(tsls), &sources)
+                .into_iter()
+                .map(|g| {
+                    (
+                        g.alphabet,
+                        g.quran,
+                        g.input,
+                        g.source,
+                        g.quran_range,
+                        g.contextual,
+                    )
+                }),
+        );
+        let expected = [
+            ("bi", "بِ", 0..2, 0..2, 0..4, false),
+            ("", "ا", 2..2, 2..2, 4..6, false),
+            ("", "ل", 2..2, 2..2, 6..8, true),
+            ("la", "لَّ", 2..4, 3..5, 8..14, true),
+            ("hi", "هِ", 4..6, 5..7, 14..18, false),
+        ];
+        let expected =
+            expected.map(|(a, q, i, s, r, c)| (a.to_string(), q.to_string(), i, s, r, c));
+        assert_eq!(groups, expected);
+        assert!(explain("", &[], &[]).is_empty());
+    }
+}
@@ -23,16 +23,71 @@
 //! assert_eq!((page, sura, aya), (582, 78, 1));
 //! ```
 
+mod analytics;
+#[cfg(feature = "audio")]
+mod audio;
+mod budget;
+mod encode_iter;
+mod explanation;
+mod line_layout;
+#[cfg(feature = "morphology")]
+mod morphology;
+mod multi_phrase;
+mod mutashabihat;
 mod normalization;
+mod page_layout;
+mod pagination;
+mod pattern;
 mod quran_metadata;
+#[cfg(feature = "regex")]
+mod regex_search;
+mod sajdah;
+mod scope;
 mod suffix_tree;
+mod tafsir;
 mod transliteration;
+mod words;
 
-use suffix_tree::{Edge, Index, SuffixTree};
-use transliteration::{contextual_map, harf_muqottoah_map, map};
+use std::{
+    borrow::Cow,
+    time::{Duration, Instant},
+};
+
+use budget::Tracker;
+use encode_iter::EncodeIter;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use suffix_tree::{Index, SuffixTree};
+use transliteration::Reading;
+
+pub use analytics::{Grouping, NgramStats, WordForm};
+#[cfg(feature = "audio")]
+pub use audio::AudioTiming;
+pub use budget::{CancellationToken, EncodeBudget};
+pub use explanation::Explanation;
+pub use line_layout::{LineLayout, MushafLine};
+#[cfg(feature = "morphology")]
+pub use morphology::{Morphology, WordAnalysis};
+pub use multi_phrase::PhraseQuery;
+pub use page_layout::PageLayout;
+#[cfg(feature = "regex")]
+pub use regex_search::RegexQuery;
+pub use sajdah::{Madhhab, SajdahKind};
+pub use scope::SearchScope;
+pub use tafsir::TafsirStore;
+pub use transliteration::statistics::{HamzaForms, LetterRules, TextStats};
+pub use transliteration::tajweed::TajweedRule;
+pub use transliteration::waqf::WaqfMark;
+pub use words::WordLocation;
 
 type EncodeResults = Vec<(String, usize, Vec<&'static str>)>;
-type PrevMap = (char, &'static str);
+type ExplainedEncodeResults = Vec<(String, usize, Vec<Explanation>)>;
+type LocatedEncodeResults = Vec<(String, Vec<Index>, Vec<&'static str>)>;
+type RawEncodeResult = (String, VertexCount, Vec<Tsl>, bool);
+type RawEncodeResults = Vec<RawEncodeResult>;
+type Tsl = (&'static str, bool);
+type VertexCount = (usize, usize);
+type NormalizedKey = (String, String);
 
 use quran_metadata::*;
 const QURAN_TXT: &str = include_str!("quran-simple.txt");
@@ -41,42 +96,153 @@ const QURAN_TXT: &str = include_str!("quran-simple.txt");
 pub struct Quranize {
     data: Vec<(u16, u8, u16, &'static str)>,
     tree: suffix_tree::SuffixTree<'static>,
+    basmalah: bool,
+    pages: PageLayout,
 }
 
 impl Quranize {
     const EXPECTED_VERTEX_COUNT: usize = 127_793;
 
     /// Create a new [`Quranize`] instance.
+    ///
+    /// The basmalah prefixed to the first aya of each sura, except sura 1 and 9, is left out of
+    /// searches and aya texts, see [`with_basmalah`](Self::with_basmalah).
     pub fn new() -> Self {
+        Self::with_basmalah(false)
+    }
+
+    /// Create a new [`Quranize`] instance, choosing whether searches and aya texts include the
+    /// basmalah prefixed to the first aya of each sura that [`has_basmalah`](Self::has_basmalah).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::Quranize;
+    ///
+    /// let q = Quranize::with_basmalah(true);
+    /// assert_eq!(q.find("بِسْمِ اللَّهِ الرَّحْمَٰنِ الرَّحِيمِ").len(), 112);
+    /// assert!(q.get_data(7).unwrap().3.starts_with("بِسْمِ اللَّهِ"));
+    /// assert_eq!(Quranize::new().find("بِسْمِ اللَّهِ الرَّحْمَٰنِ الرَّحِيمِ").len(), 2);
+    /// ```
+    pub fn with_basmalah(basmalah: bool) -> Self {
         let mut data = Vec::with_capacity(AYA_COUNT);
         let mut tree = SuffixTree::with_capacity(Self::EXPECTED_VERTEX_COUNT);
 
+        let pages = PageLayout::madinah();
         let mut sura_num = 0;
-        let mut page = 0;
         (0..AYA_COUNT)
             .zip(QURAN_TXT.split_inclusive('\n'))
             .map(|(i, q)| {
                 sura_num += (SURA_STARTS.get(sura_num) == Some(&i)) as usize;
                 let aya_num = i - SURA_STARTS[sura_num - 1] + 1;
-                page += (PAGE_OFFSETS.get(page) == Some(&(sura_num, aya_num))) as usize;
-                (i, page as u16, sura_num as u8, aya_num as u16, q)
+                let page = pages.page(i).unwrap();
+                (i, page, sura_num as u8, aya_num as u16, q)
             })
-            .map(|(i, p, s, a, q)| (i, p, s, a, Self::trim_basmalah(s, a, q)))
-            .for_each(|(i, p, s, a, q)| {
+            .map(|(i, p, s, a, q)| match basmalah {
+                true => (i, p, s, a, q, Self::basmalah_len(s, a, q)),
+                false => (i, p, s, a, &q[Self::basmalah_len(s, a, q)..], 0),
+            })
+            .for_each(|(i, p, s, a, q, start)| {
                 data.push((p, s, a, q.trim_end()));
-                tree.construct(i, q);
+                tree.construct(i, q, start);
             });
-        Self { data, tree }
+        Self {
+            data,
+            tree,
+            basmalah,
+            pages,
+        }
+    }
+
+    /// Use page layout `pages` for the page numbers in [`get_data`](Self::get_data) and
+    /// [`get_data_from_page`](Self::get_data_from_page), for [`page_scope`](Self::page_scope),
+    /// and for page filters of regex searches, instead of the default Madinah layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{PageLayout, Quranize};
+    ///
+    /// let pages = PageLayout::parse("1:1\n2:1\n2:17").unwrap();
+    /// let q = Quranize::new().with_page_layout(&pages);
+    /// assert_eq!(q.get_data(12).unwrap().0, 2);
+    /// assert_eq!(q.get_data_from_page(2).unwrap().len(), 16);
+    /// assert_eq!(q.get_data_from_page(4), None);
+    /// assert_eq!(q.page_layout(), &pages);
+    /// ```
+    pub fn with_page_layout(mut self, pages: &PageLayout) -> Self {
+        for (i, (page, _, _, _)) in self.data.iter_mut().enumerate() {
+            *page = pages.page(i).unwrap();
+        }
+        self.pages = pages.clone();
+        self
     }
 
-    fn trim_basmalah(s: u8, a: u16, q: &str) -> &str {
-        match (s, a) {
-            (1, _) | (9, _) => q,
-            (_, 1) => q.splitn(5, ' ').last().unwrap(),
-            _ => q,
+    /// Get the page layout in use, see [`with_page_layout`](Self::with_page_layout).
+    pub fn page_layout(&self) -> &PageLayout {
+        &self.pages
+    }
+
+    /// Create a scope covering page number `page` of the page layout in use, for
+    /// [`find_in`](Self::find_in) and [`encode_in`](Self::encode_in). Other numbers give an
+    /// empty scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{PageLayout, Quranize, SearchScope};
+    ///
+    /// let q = Quranize::new();
+    /// assert_eq!(q.page_scope(2), SearchScope::page(2));
+    /// let q = q.with_page_layout(&PageLayout::parse("1:1\n2:1\n2:17").unwrap());
+    /// assert_eq!(q.page_scope(2), SearchScope::new(Some(7..23)));
+    /// assert_eq!(q.find_in("الم", &q.page_scope(2)).len(), 1);
+    /// ```
+    pub fn page_scope(&self, page: u16) -> SearchScope {
+        self.pages.scope(page)
+    }
+
+    fn basmalah_len(s: u8, a: u16, q: &str) -> usize {
+        match (Self::has_basmalah(s), a) {
+            (true, 1) => q.len() - q.splitn(5, ' ').last().unwrap().len(),
+            _ => 0,
         }
     }
 
+    /// Check whether the first aya of sura number `sura` (`1..=114`) is prefixed with a basmalah
+    /// that is not counted as an aya, i.e. every sura except 1, where the basmalah is aya 1:1,
+    /// and 9, which has no basmalah.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::Quranize;
+    ///
+    /// assert!(Quranize::has_basmalah(2));
+    /// assert!(!Quranize::has_basmalah(1) && !Quranize::has_basmalah(9));
+    /// assert!(!Quranize::has_basmalah(115));
+    /// ```
+    pub fn has_basmalah(sura: u8) -> bool {
+        matches!(sura, 2..=8 | 10..=114)
+    }
+
+    /// Check whether location `(i, j)`, e.g. from [`find`](Self::find), is inside the basmalah
+    /// prefixed to an aya text. Always `false` if the basmalah is left out, see
+    /// [`with_basmalah`](Self::with_basmalah).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::with_basmalah(true);
+    /// let locations = q.find("بِسْمِ اللَّهِ");
+    /// let prefixed = locations.iter().filter(|&&l| q.is_in_basmalah(l)).count();
+    /// assert_eq!((locations.len(), prefixed), (113, 110));
+    /// ```
+    pub fn is_in_basmalah(&self, (i, j): (usize, usize)) -> bool {
+        self.basmalah && { self.data.get(i) }
+            .is_some_and(|&(_, s, a, q)| j < Self::basmalah_len(s, a, q))
+    }
+
     /// Do transliteration on `s`, returning a list of tuple:
     /// - `String`: transliteration result / quran form
     /// - `usize`: location count where the quran form above is found in Alquran
@@ -90,79 +256,192 @@ impl Quranize {
     /// assert_eq!(q.encode("minal jinnati wannas")[0].0, "مِنَ الْجِنَّةِ وَالنَّاس");
     /// ```
     pub fn encode(&self, s: &str) -> EncodeResults {
-        let mut results: EncodeResults = match normalization::normalize(s).as_str() {
-            "" => vec![],
-            s => { self.tree.edges_from(0) }
-                .flat_map(|&e| self.rev_encode(s, e, None))
-                .collect(),
-        }
-        .into_iter()
-        .chain(match normalization::normalize_muqottoah(s).as_str() {
-            "" => vec![],
-            s => { self.tree.edges_from(0) }
-                .flat_map(|&e| self.rev_encode_muqottoah(s, e))
-                .collect(),
-        })
-        .map(|(q, n, e)| (q.chars().rev().collect(), n, e.into_iter().rev().collect()))
-        .collect();
+        Self::strip_contexts(self.encode_normalized(&Self::normalize_key(s)))
+    }
+
+    /// Do transliteration on `s` like [`encode`](Self::encode), but explain each result as a list
+    /// of [`Explanation`], one for each base char of the quran form together with its harakat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (quran, _, explanations) = &q.encode_with_explanations("waladdoollin")[0];
+    /// assert_eq!(quran, "وَلَا الضَّالِّين");
+    /// let e = &explanations[6];
+    /// assert_eq!((e.alphabet.as_str(), e.quran.as_str(), e.input.clone()), ("ddo", "ضَّ", 4..7));
+    /// assert!(e.contextual);
+    /// assert!(!explanations[0].contextual);
+    ///
+    /// let text = "Wa-la ddoollin!";
+    /// let (quran, _, explanations) = &q.encode_with_explanations(text)[0];
+    /// let e = &explanations[6];
+    /// assert_eq!((&text[e.source.clone()], &quran[e.quran_range.clone()]), ("ddo", "ضَّ"));
+    /// ```
+    pub fn encode_with_explanations(&self, s: &str) -> ExplainedEncodeResults {
+        let (norm, sources) = normalization::normalize_with_ranges(s);
+        let (norm_muq, sources_muq) = normalization::normalize_muqottoah_with_ranges(s);
+        { self.encode_normalized(&(norm, norm_muq)).into_iter() }
+            .map(|(q, (_, n), e, muq)| {
+                let sources = if muq { &sources_muq } else { &sources };
+                let explanations = explanation::explain(&q, &e, sources);
+                (q, n, explanations)
+            })
+            .collect()
+    }
+
+    /// Do transliteration on `s` like [`encode`](Self::encode), but return the locations of each
+    /// quran form instead of their count, so there is no need to [`find`](Self::find) it again.
+    /// Locations are collected from where the quran form ends in the suffix tree,
+    /// in the same order as [`find`](Self::find).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (quran, locations, _) = &q.encode_with_locations("alif lam mim")[0];
+    /// assert_eq!(quran, "الم");
+    /// assert_eq!(locations.len(), 8);
+    /// assert!(locations.contains(&(7, 0)));
+    /// ```
+    pub fn encode_with_locations(&self, s: &str) -> LocatedEncodeResults {
+        { self.encode_normalized(&Self::normalize_key(s)).into_iter() }
+            .map(|(q, (w, _), e, _)| (q, self.tree.collect_data(w), Self::strip_context(e)))
+            .collect()
+    }
+
+    /// Do transliteration on every text in `texts`, returning the [`encode`](Self::encode) results
+    /// in the same order as `texts`, each paired with the time spent encoding it.
+    ///
+    /// Texts that are identical after normalization are encoded only once and share their results.
+    /// With the `rayon` feature enabled, distinct texts are encoded in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let results = q.encode_many(&["bismillah", "Bismillah!", "alif lam mim"]);
+    /// assert_eq!(results.len(), 3);
+    /// assert_eq!(results[0].0, results[1].0);
+    /// assert_eq!(results[2].0[0].0, "الم");
+    /// ```
+    pub fn encode_many<S: AsRef<str> + Sync>(&self, texts: &[S]) -> Vec<(EncodeResults, Duration)> {
+        let keys = Vec::from_iter(texts.iter().map(|s| Self::normalize_key(s.as_ref())));
+        let mut unique_keys = Vec::from_iter(keys.iter());
+        unique_keys.sort_unstable();
+        unique_keys.dedup();
+        let encode_timed = |&k: &&NormalizedKey| {
+            let start = Instant::now();
+            let results = Self::strip_contexts(self.encode_normalized(k));
+            (results, start.elapsed())
+        };
+        #[cfg(feature = "rayon")]
+        let unique_results: Vec<_> = unique_keys.par_iter().map(encode_timed).collect();
+        #[cfg(not(feature = "rayon"))]
+        let unique_results: Vec<_> = unique_keys.iter().map(encode_timed).collect();
+        { keys.iter() }
+            .map(|k| unique_results[unique_keys.binary_search(&k).unwrap()].clone())
+            .collect()
+    }
+
+    fn normalize_key(s: &str) -> NormalizedKey {
+        (
+            normalization::normalize(s),
+            normalization::normalize_muqottoah(s),
+        )
+    }
+
+    /// Do transliteration on `s` like [`encode`](Self::encode), but give up once `budget` is
+    /// exhausted or `token` is cancelled, returning a tuple of:
+    /// - `EncodeResults`: the results found so far, in the same form as [`encode`](Self::encode)
+    /// - `bool`: whether the search was truncated before exploring every possibility
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{CancellationToken, EncodeBudget, Quranize};
+    ///
+    /// let q = Quranize::new();
+    /// let token = CancellationToken::new();
+    /// let (results, truncated) = q.encode_with_budget("bismillah", &Default::default(), &token);
+    /// assert_eq!((results, truncated), (q.encode("bismillah"), false));
+    ///
+    /// let budget = EncodeBudget { max_visited_states: Some(10), ..Default::default() };
+    /// let (_, truncated) = q.encode_with_budget("bismillah", &budget, &token);
+    /// assert!(truncated);
+    ///
+    /// token.cancel();
+    /// assert_eq!(q.encode_with_budget("bismillah", &Default::default(), &token), (vec![], true));
+    /// ```
+    pub fn encode_with_budget(
+        &self,
+        s: &str,
+        budget: &EncodeBudget,
+        token: &CancellationToken,
+    ) -> (EncodeResults, bool) {
+        let tracker = Tracker::new(Some(budget), Some(token));
+        let key = Self::normalize_key(s);
+        let (results, truncated) = self.encode_tracked(&key, tracker, &SearchScope::all());
+        (Self::strip_contexts(results), truncated)
+    }
+
+    /// Do transliteration on `s` like [`encode`](Self::encode), but only count locations inside
+    /// `scope`. Quran forms without any location inside `scope` are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{Quranize, SearchScope};
+    ///
+    /// let q = Quranize::new();
+    /// assert_eq!(q.encode("robbil alamin").len(), 2);
+    /// let (quran, count, _) = &q.encode_in("robbil alamin", &SearchScope::sura(1))[0];
+    /// assert_eq!((quran.as_str(), *count), ("رَبِّ الْعَالَمِين", 1));
+    /// ```
+    pub fn encode_in(&self, s: &str, scope: &SearchScope) -> EncodeResults {
+        let key = Self::normalize_key(s);
+        Self::strip_contexts(self.encode_tracked(&key, Tracker::unlimited(), scope).0)
+    }
+
+    fn strip_contexts(results: RawEncodeResults) -> EncodeResults {
+        { results.into_iter() }
+            .map(|(q, (_, n), e, _)| (q, n, Self::strip_context(e)))
+            .collect()
+    }
+
+    fn strip_context(tsls: Vec<Tsl>) -> Vec<&'static str> {
+        tsls.into_iter().map(|(tsl, _)| tsl).collect()
+    }
+
+    fn encode_normalized(&self, k: &NormalizedKey) -> RawEncodeResults {
+        self.encode_tracked(k, Tracker::unlimited(), &SearchScope::all())
+            .0
+    }
+
+    /// Walk the suffix tree reading `k` plainly and as harf muqottoah, returning the results
+    /// sorted by quran form, and whether `t` stopped the walk early.
+    fn encode_tracked(
+        &self,
+        k: &NormalizedKey,
+        t: Tracker,
+        sc: &SearchScope,
+    ) -> (RawEncodeResults, bool) {
+        let readings = [Reading::Plain, Reading::Muqottoah];
+        let mut iter = EncodeIter::new(self, k.clone(), &readings, t, Cow::Borrowed(sc));
+        let mut results = Vec::from_iter(iter.by_ref());
         results.sort_unstable_by(|x, y| x.0.cmp(&y.0));
-        results.dedup_by(|x, y| x.0 == y.0);
-        results
-    }
-
-    fn rev_encode(&self, s: &str, (v, w, l): Edge, pm: Option<PrevMap>) -> EncodeResults {
-        let results_iter = l.chars().next().into_iter().flat_map(|c| -> EncodeResults {
-            let tsls = map(c).iter().chain(contextual_map(pm.unzip().0, c));
-            let tsl_results_iter = tsls.filter_map(|&tsl| -> Option<EncodeResults> {
-                s.strip_prefix(tsl).map(|s| match s {
-                    "" => vec![(c.to_string(), self.tree.count_data(w), vec![tsl])],
-                    s => match &l[c.len_utf8()..] {
-                        "" => { self.tree.edges_from(w) }
-                            .flat_map(|&e| self.rev_encode(s, e, Some((c, tsl))))
-                            .collect(),
-                        l => self.rev_encode(s, (v, w, l), Some((c, tsl))),
-                    }
-                    .into_iter()
-                    .map(|(mut q, n, mut e)| {
-                        q.push(c);
-                        e.push(tsl);
-                        (q, n, e)
-                    })
-                    .collect(),
-                })
-            });
-            tsl_results_iter.flatten().collect()
-        });
-        results_iter.collect()
-    }
-
-    fn rev_encode_muqottoah(&self, s: &str, (v, w, l): Edge) -> EncodeResults {
-        let results_iter = l.chars().next().into_iter().flat_map(|c| -> EncodeResults {
-            let tsls = harf_muqottoah_map(c).iter();
-            let tsl_results_iter = tsls.filter_map(|&tsl| -> Option<EncodeResults> {
-                s.strip_prefix(tsl).map(|s| match s {
-                    "" => match self.tree.vertices[w].2 {
-                        true => vec![(c.to_string(), self.tree.count_data(w), vec![tsl])],
-                        false => vec![],
-                    },
-                    s => match &l[c.len_utf8()..] {
-                        "" => { self.tree.edges_from(w) }
-                            .flat_map(|&e| self.rev_encode_muqottoah(s, e))
-                            .collect(),
-                        l => self.rev_encode_muqottoah(s, (v, w, l)),
-                    }
-                    .into_iter()
-                    .map(|(mut q, n, mut e)| {
-                        q.push(c);
-                        e.push(tsl);
-                        (q, n, e)
-                    })
-                    .collect(),
-                })
-            });
-            tsl_results_iter.flatten().collect()
-        });
-        results_iter.collect()
+        (results, iter.truncated())
+    }
+
+    /// Count locations in `sc` under vertex `w` where a reading ends, `l` being the rest of the
+    /// current edge.
+    fn count_end(&self, w: usize, l: &str, reading: Reading, sc: &SearchScope) -> usize {
+        match reading {
+            Reading::Plain | Reading::Pause(None, true) => self.tree.count_data_in(w, sc),
+            Reading::Muqottoah if self.tree.vertices[w].2 => self.tree.count_data_in(w, sc),
+            Reading::Muqottoah | Reading::Pause(None, false) => 0,
+            Reading::Pause(Some(p), _) => self.count_at_pause(w, l, p, sc),
+        }
     }
 
     /// Find `s` in Alquran, returning a list of `Index`, where
@@ -180,6 +459,20 @@ impl Quranize {
         self.tree.find(s, 0)
     }
 
+    /// Find `s` in Alquran like [`find`](Self::find), but only inside `scope`.
+    ///
+    /// # Examples
+    /// ```
+    /// use quranize::{Quranize, SearchScope};
+    ///
+    /// let q = Quranize::new();
+    /// assert_eq!(q.find_in("الرَّحْمَٰنِ الرَّحِيمِ", &SearchScope::sura(1)), [(0, 28), (2, 0)]);
+    /// assert!(q.find_in("الرَّحْمَٰنِ الرَّحِيمِ", &SearchScope::juz(30)).is_empty());
+    /// ```
+    pub fn find_in(&self, s: &str, scope: &SearchScope) -> Vec<Index> {
+        self.tree.find_in(s, 0, scope)
+    }
+
     /// Get the data for a specific aya row / aya offset (`i`: 0..6236).
     /// Data is a tuple of:
     /// - `u16`: page number
@@ -196,7 +489,8 @@ impl Quranize {
         self.data.get(i)
     }
 
-    /// Get the data for a specific page number (`page`: 1..604).
+    /// Get the data for a specific page number (`page`: 1..604, or the page count of the layout
+    /// given to [`with_page_layout`](Self::with_page_layout)).
     /// Returns a vector of tuples, each tuple contains:
     /// - `u16`: page number
     /// - `u8`: sura number
@@ -217,6 +511,19 @@ impl Quranize {
         let page_data = self.data[left_pos..].iter().take_while(same_page).collect();
         Some(page_data)
     }
+
+    /// Get the juz number (`1..=30`) for a specific aya row / aya offset (`i`: 0..6236).
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// assert_eq!(q.get_juz(0), Some(1));
+    /// assert_eq!(q.get_juz(5672), Some(30));
+    /// assert_eq!(q.get_juz(6236), None);
+    /// ```
+    pub fn get_juz(&self, i: usize) -> Option<u8> {
+        (i < self.data.len()).then(|| JUZ_STARTS.partition_point(|&j| j <= i) as u8)
+    }
 }
 
 impl Default for Quranize {
@@ -278,6 +585,119 @@ mod tests {
         assert_eq!(q.e("wassolat"), ["وَالصَّلَاة"]);
     }
 
+    #[test]
+    fn test_encode_many() {
+        let q = Quranize::new();
+        let texts = ["bismillah", "alif lam mim", "Bis-millah", "", "bismillah"];
+        let results = q.encode_many(&texts);
+        assert_eq!(results.len(), texts.len());
+        for (text, (r, _)) in texts.iter().zip(&results) {
+            assert_eq!(r, &q.encode(text));
+        }
+        assert_eq!(results[0], results[2]);
+        assert_eq!(results[0], results[4]);
+        assert!(results[3].0.is_empty());
+        assert!(q.encode_many::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_encode_with_budget() {
+        let q = Quranize::new();
+        let token = CancellationToken::new();
+        let text = "wa'tasimu bihablillah";
+        let (results, truncated) = q.encode_with_budget(text, &Default::default(), &token);
+        assert_eq!(results, q.encode(text));
+        assert!(!truncated);
+
+        let budget = EncodeBudget {
+            max_visited_states: Some(50),
+            ..Default::default()
+        };
+        let (results, truncated) = q.encode_with_budget(text, &budget, &token);
+        assert!(results.is_empty());
+        assert!(truncated);
+
+        let (results, truncated) =
+            q.encode_with_budget("robbil alamin", &Default::default(), &token);
+        assert_eq!(results.len(), 2);
+        assert!(!truncated);
+        let budget = EncodeBudget {
+            max_results: Some(1),
+            ..Default::default()
+        };
+        let (results, truncated) = q.encode_with_budget("robbil alamin", &budget, &token);
+        assert_eq!(results.len(), 1);
+        assert!(truncated);
+
+        let budget = EncodeBudget {
+            deadline: Some(Instant::now()),
+            ..Default::default()
+        };
+        let (_, truncated) = q.encode_with_budget("laa ilaha illallah", &budget, &token);
+        assert!(truncated);
+    }
+
+    #[test]
+    fn test_encode_with_locations() {
+        let q = Quranize::new();
+        for text in [
+            "bismillah",
+            "alif lam mim",
+            "qul",
+            "robbil alamin",
+            "wassolat",
+            "",
+        ] {
+            let results = q.encode_with_locations(text);
+            let expected = q.encode(text);
+            assert_eq!(results.len(), expected.len());
+            for ((quran, locations, e), (expected_quran, n, expected_e)) in
+                results.iter().zip(expected)
+            {
+                assert_eq!((quran, e), (&expected_quran, &expected_e));
+                assert_eq!(locations.len(), n);
+                assert_eq!(locations, &q.find(quran));
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_in() {
+        let q = Quranize::new();
+        for text in ["bismillah", "alif lam mim", "qul", "robbil alamin"] {
+            assert_eq!(q.encode_in(text, &SearchScope::all()), q.encode(text));
+        }
+        let juz_30 = SearchScope::juz(30);
+        for (quran, n, _) in q.encode_in("qul", &juz_30) {
+            let locations = q
+                .find(&quran)
+                .into_iter()
+                .filter(|&(i, _)| juz_30.contains(i));
+            assert_eq!(n, locations.count());
+        }
+        let results = q.encode_in(
+            "alif lam mim",
+            &SearchScope::sura(2).union(&SearchScope::sura(3)),
+        );
+        assert_eq!(
+            results,
+            [("الم".to_string(), 2, vec!["alif", "lam", "mim"])]
+        );
+        assert!(q.encode_in("alif lam mim", &juz_30).is_empty());
+        assert!(q.encode_in("bismillah", &SearchScope::new([])).is_empty());
+    }
+
+    #[test]
+    fn test_find_in() {
+        let q = Quranize::new();
+        for s in ["بِسْمِ", "قُلْ", "الم"] {
+            assert_eq!(q.find_in(s, &SearchScope::all()), q.find(s));
+            let scope = SearchScope::new([0..100, 5000..5100]);
+            let expected = q.find(s).into_iter().filter(|&(i, _)| scope.contains(i));
+            assert_eq!(q.find_in(s, &scope), Vec::from_iter(expected));
+        }
+    }
+
     #[test]
     fn test_alfatihah() {
         let q = Quranize::new();
@@ -380,6 +800,65 @@ mod tests {
         assert_eq!(set.len(), 55);
     }
 
+    #[test]
+    fn test_get_juz() {
+        let q = Quranize::new();
+        assert_eq!(q.get_juz(147), Some(1));
+        assert_eq!(q.get_juz(148), Some(2));
+        assert_eq!(q.get_data(148).map(|&(_, s, a, _)| (s, a)), Some((2, 142)));
+        assert_eq!(q.get_juz(AYA_COUNT - 1), Some(30));
+        let juz_counts = (0..AYA_COUNT).fold([0; 30], |mut counts, i| {
+            counts[q.get_juz(i).unwrap() as usize - 1] += 1;
+            counts
+        });
+        assert!(juz_counts.iter().all(|&n| n > 0));
+        assert_eq!(juz_counts[29], 564);
+    }
+
+    #[test]
+    fn test_with_basmalah() {
+        let q = Quranize::with_basmalah(true);
+        let plain = Quranize::new();
+        let basmalah = "بِسْمِ اللَّهِ الرَّحْمَٰنِ الرَّحِيمِ ";
+        assert_eq!(
+            q.get_data(7).unwrap().3,
+            format!("{basmalah}{}", plain.get_data(7).unwrap().3)
+        );
+        assert_eq!(q.get_data(0), plain.get_data(0));
+        assert_eq!(q.get_data(SURA_STARTS[8]), plain.get_data(SURA_STARTS[8]));
+        assert!(q.is_in_basmalah((7, 0)) && q.is_in_basmalah((7, basmalah.len() - 1)));
+        assert!(!q.is_in_basmalah((7, basmalah.len())) && !q.is_in_basmalah((0, 0)));
+        assert!(!plain.is_in_basmalah((7, 0)));
+        assert!(!q.is_in_basmalah((AYA_COUNT, 0)));
+        assert!(q.encode("bismillahirrohmanirrohim")[0].1 > 100);
+        assert_eq!(q.encode("alif lam mim"), plain.encode("alif lam mim"));
+        assert_eq!(q.encode("alif lam mim")[0].1, 8);
+        assert_eq!(
+            q.encode("kaf ha ya 'ain shod"),
+            plain.encode("kaf ha ya 'ain shod")
+        );
+        assert_eq!(
+            (1..=114).filter(|&s| Quranize::has_basmalah(s)).count(),
+            112
+        );
+    }
+
+    #[test]
+    fn test_with_page_layout() {
+        let q = Quranize::new();
+        let pages = PageLayout::parse("1:1\n2:1\n3:1").unwrap();
+        let q = q.with_page_layout(&pages);
+        let s = "الْحَيُّ الْقَيُّومُ";
+        assert!(q.find_in(s, &SearchScope::page(2)).is_empty());
+        assert_eq!(
+            q.find_in(s, &q.page_scope(2)),
+            q.find_in(s, &SearchScope::sura(2))
+        );
+        assert_eq!(q.get_data_from_page(3).unwrap()[0].1, 3);
+        assert_eq!(q.find_in(s, &q.page_scope(2)).len(), 1);
+        assert_eq!(q.page_scope(4), SearchScope::new([]));
+    }
+
     #[test]
     fn test_get_data_from_page() {
         let q = Quranize::new();
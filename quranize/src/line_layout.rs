@@ -0,0 +1,260 @@
+use std::ops::Range;
+
+use crate::{Quranize, WordLocation, suffix_tree::is_separator, words::aya_row};
+
+type LineSpans = Vec<(usize, Range<usize>)>;
+
+/// Line of a mushaf page, see [`LineLayout`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MushafLine {
+    /// Header of sura number `.0`.
+    SuraHeader(u8),
+    /// Basmalah written before the first aya of a sura.
+    Basmalah,
+    /// Words from the first to the last location, both inclusive, possibly across ayas.
+    /// The aya texts of the words are given by [`Quranize::get_line_spans`].
+    Words(WordLocation, WordLocation),
+}
+
+/// Line-by-line layout of mushaf pages, for rendering a page the way it is printed, see
+/// [`Quranize::get_page_lines`].
+///
+/// No line data is bundled with this crate, not even for the default Madinah mushaf, so a
+/// layout is always loaded from a dataset with [`parse`](Self::parse). Word numbers in the
+/// dataset are Uthmani word numbers, as in most line-by-line datasets, see [`WordLocation`].
+///
+/// # Examples
+///
+/// ```
+/// use quranize::{LineLayout, MushafLine};
+///
+/// let layout = LineLayout::parse("1,1,sura,1
+/// 1,2,words,1:1:1,1:2:2
+/// 1,3,words,1:2:3,1:4:3
+/// 2,1,sura,2
+/// 2,2,basmalah
+/// 2,3,words,2:1:1,2:2:4").unwrap();
+/// assert_eq!(layout.page_count(), 2);
+/// assert_eq!(layout.get_page(2).unwrap()[1], MushafLine::Basmalah);
+/// assert_eq!(layout.get_page(3), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineLayout {
+    pages: Vec<Vec<MushafLine>>,
+}
+
+impl LineLayout {
+    /// Parse a line layout from comma separated lines of `page,line,kind,...`, one per mushaf
+    /// line in order, where `page` and `line` start from 1, and `kind` is one of:
+    /// - `sura,<sura>` for a sura header
+    /// - `basmalah` for a basmalah line
+    /// - `words,<sura:aya:word>,<sura:aya:word>` for the first and the last word of a line
+    ///
+    /// Empty lines and comment lines starting with `#` are skipped.
+    /// Returns the line number (starting from 1) of the first invalid line as an error.
+    pub fn parse(text: &str) -> Result<Self, usize> {
+        let mut layout = Self::default();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            layout.push(line).ok_or(n + 1)?;
+        }
+        Ok(layout)
+    }
+
+    fn push(&mut self, line: &str) -> Option<()> {
+        let fields = Vec::from_iter(line.split(',').map(str::trim));
+        let page = fields.first()?.parse::<usize>().ok()?;
+        let line = fields.get(1)?.parse::<usize>().ok()?;
+        let mushaf_line = match fields[2..] {
+            ["sura", sura] => {
+                MushafLine::SuraHeader(sura.parse().ok().filter(|s| aya_row(*s, 1).is_some())?)
+            }
+            ["basmalah"] => MushafLine::Basmalah,
+            ["words", first, last] => {
+                let (first, last) = (parse_word_location(first)?, parse_word_location(last)?);
+                (first <= last).then_some(MushafLine::Words(first, last))?
+            }
+            _ => None?,
+        };
+        match (page, line) {
+            (p, 1) if p == self.pages.len() + 1 => self.pages.push(vec![mushaf_line]),
+            (p, l) if p > 0 && p == self.pages.len() && l == self.pages[p - 1].len() + 1 => {
+                self.pages[p - 1].push(mushaf_line)
+            }
+            _ => None?,
+        }
+        Some(())
+    }
+
+    /// Get the number of pages.
+    pub fn page_count(&self) -> u16 {
+        self.pages.len() as u16
+    }
+
+    /// Get the lines of page number `page` (starting from 1), in order.
+    pub fn get_page(&self, page: u16) -> Option<&[MushafLine]> {
+        self.pages
+            .get((page as usize).checked_sub(1)?)
+            .map(Vec::as_slice)
+    }
+}
+
+fn parse_word_location(location: &str) -> Option<WordLocation> {
+    let numbers = Vec::from_iter(location.split(':'));
+    let location = match numbers[..] {
+        [s, a, w] => (s.parse().ok()?, a.parse().ok()?, w.parse().ok()?),
+        _ => None?,
+    };
+    aya_row(location.0, location.1).filter(|_| location.2 > 0)?;
+    Some(location)
+}
+
+impl Quranize {
+    /// Get the aya texts of the words from location `first` to `last`, both inclusive, e.g. of a
+    /// [`MushafLine::Words`], returning a list of tuple, one for each aya, in order:
+    /// - `usize`: aya row (`0..6236`)
+    /// - `Range<usize>`: byte range of the words in the aya text from [`get_data`](Self::get_data)
+    ///
+    /// Waqf marks after the last word are included. Returns `None` if a word does not exist or
+    /// `first` comes after `last`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let spans = q.get_line_spans((2, 2, 3), (2, 3, 1)).unwrap();
+    /// assert_eq!(spans.len(), 2);
+    /// let (i, range) = spans[0].clone();
+    /// assert_eq!(&q.get_data(i).unwrap().3[range], "لَا رَيْبَ ۛ فِيهِ ۛ هُدًى لِّلْمُتَّقِينَ");
+    /// let (i, range) = spans[1].clone();
+    /// assert_eq!(&q.get_data(i).unwrap().3[range], "الَّذِينَ");
+    /// ```
+    pub fn get_line_spans(&self, first: WordLocation, last: WordLocation) -> Option<LineSpans> {
+        let (i, j) = self.get_word_index(first)?;
+        let (last_i, last_range) = self.get_word_range(last)?;
+        if (i, j) > (last_i, last_range.start) {
+            return None;
+        }
+        let spans = (i..=last_i).map(|k| {
+            let &(_, sura, aya, text) = &self.data[k];
+            let start = match k == i {
+                true => j,
+                false => self.get_word_index((sura, aya, 1)).map_or(0, |(_, j)| j),
+            };
+            let end = match k == last_i {
+                true => Self::end_with_marks(text, last_range.end),
+                false => text.len(),
+            };
+            (k, start..end)
+        });
+        Some(spans.collect())
+    }
+
+    /// Get the lines of page number `page` of `layout`, each with the aya texts of its words
+    /// like [`get_line_spans`](Self::get_line_spans), or no spans for sura headers and basmalah
+    /// lines. Returns `None` if the page does not exist or a word of it does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{LineLayout, MushafLine, Quranize};
+    ///
+    /// let layout = LineLayout::parse("1,1,sura,2\n1,2,words,2:1:1,2:2:2").unwrap();
+    /// let q = Quranize::new();
+    /// let lines = q.get_page_lines(&layout, 1).unwrap();
+    /// assert_eq!(lines[0], (MushafLine::SuraHeader(2), vec![]));
+    /// let texts = Vec::from_iter(lines[1].1.iter().map(|(i, r)| &q.get_data(*i).unwrap().3[r.clone()]));
+    /// assert_eq!(texts, ["الم", "ذَٰلِكَ الْكِتَابُ"]);
+    /// assert_eq!(q.get_page_lines(&layout, 2), None);
+    /// ```
+    pub fn get_page_lines(
+        &self,
+        layout: &LineLayout,
+        page: u16,
+    ) -> Option<Vec<(MushafLine, LineSpans)>> {
+        { layout.get_page(page)?.iter() }
+            .map(|&line| match line {
+                MushafLine::Words(first, last) => Some((line, self.get_line_spans(first, last)?)),
+                _ => Some((line, vec![])),
+            })
+            .collect()
+    }
+
+    /// Byte offset in `text` right after the word ending at `j` and the waqf marks after it.
+    fn end_with_marks(text: &str, j: usize) -> usize {
+        let rest = &text[j..];
+        let marks = rest.find(|c| !is_separator(c)).unwrap_or(rest.len());
+        j + rest[..marks].trim_end().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let layout = LineLayout::parse("# page 1\n1,1,sura,1\n\n1,2,words,1:1:1,1:1:4\n").unwrap();
+        assert_eq!(
+            layout.get_page(1),
+            Some(
+                &[
+                    MushafLine::SuraHeader(1),
+                    MushafLine::Words((1, 1, 1), (1, 1, 4))
+                ][..]
+            )
+        );
+        assert_eq!(layout.get_page(0), None);
+        assert_eq!(LineLayout::parse(""), Ok(LineLayout::default()));
+
+        assert_eq!(LineLayout::parse("1,2,basmalah"), Err(1));
+        assert_eq!(LineLayout::parse("2,1,basmalah"), Err(1));
+        assert_eq!(LineLayout::parse("0,2,basmalah"), Err(1));
+        assert_eq!(LineLayout::parse("1,1,basmalah\n1,1,basmalah"), Err(2));
+        assert_eq!(LineLayout::parse("1,1,basmalah\n1,3,basmalah"), Err(2));
+        assert_eq!(LineLayout::parse("1,1,basmalah\n2,2,basmalah"), Err(2));
+        assert_eq!(LineLayout::parse("1,1,sura,115"), Err(1));
+        assert_eq!(LineLayout::parse("1,1,words,1:2:1,1:1:4"), Err(1));
+        assert_eq!(LineLayout::parse("1,1,words,1:8:1,1:8:2"), Err(1));
+        assert_eq!(LineLayout::parse("1,1,words,1:1:0,1:1:2"), Err(1));
+        assert_eq!(LineLayout::parse("1,1,words,1:1:1"), Err(1));
+        assert_eq!(LineLayout::parse("1,1,title"), Err(1));
+    }
+
+    #[test]
+    fn test_get_line_spans() {
+        let q = Quranize::new();
+        let texts = |first, last| {
+            Vec::from_iter(
+                { q.get_line_spans(first, last).unwrap().into_iter() }
+                    .map(|(i, range)| &q.get_data(i).unwrap().3[range]),
+            )
+        };
+        assert_eq!(texts((1, 1, 4), (1, 1, 4)), ["الرَّحِيمِ"]);
+        assert_eq!(texts((1, 1, 3), (1, 2, 2)), ["الرَّحْمَٰنِ الرَّحِيمِ", "الْحَمْدُ لِلَّهِ"]);
+        assert_eq!(texts((2, 2, 1), (2, 2, 4)), ["ذَٰلِكَ الْكِتَابُ لَا رَيْبَ ۛ"]);
+        assert_eq!(texts((1, 7, 9), (2, 1, 1)).len(), 2);
+        assert_eq!(texts((1, 7, 9), (2, 1, 1))[1], "الم");
+        assert_eq!(q.get_line_spans((1, 2, 1), (1, 1, 1)), None);
+        assert_eq!(q.get_line_spans((1, 1, 1), (1, 1, 5)), None);
+        assert_eq!(texts((2, 21, 1), (2, 21, 1)), ["يَا أَيُّهَا"]);
+        assert_eq!(texts((2, 20, 25), (2, 21, 2)), ["قَدِيرٌ", "يَا أَيُّهَا النَّاسُ"]);
+        assert_eq!(texts((20, 94, 2), (20, 94, 2)), ["يَا ابْنَ أُمَّ"]);
+
+        let layout = "1,1,sura,1\n1,2,words,1:1:1,1:1:4\n2,1,words,1:1:1,1:1:5";
+        let layout = LineLayout::parse(layout).unwrap();
+        let lines = q.get_page_lines(&layout, 1).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].1, [(0, 0..q.get_data(0).unwrap().3.len())]);
+        assert_eq!(q.get_page_lines(&layout, 0), None);
+        assert_eq!(q.get_page_lines(&layout, 2), None);
+
+        let q = Quranize::with_basmalah(true);
+        let spans = q.get_line_spans((1, 7, 9), (2, 1, 1)).unwrap();
+        assert_eq!(&q.get_data(7).unwrap().3[spans[1].1.clone()], "الم");
+    }
+}
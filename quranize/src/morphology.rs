@@ -0,0 +1,383 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{WordLocation, normalization, transliteration::map};
+
+/// Morphological analysis of one word, see [`Morphology`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordAnalysis {
+    /// Word form in Buckwalter transliteration, all segments joined, e.g. `"bisomi"`.
+    pub form: String,
+    /// Root of the stem in Buckwalter transliteration, e.g. `"smw"`, if the word has one.
+    pub root: Option<String>,
+    /// Lemma of the stem in Buckwalter transliteration, e.g. `"{som"`, if the word has one.
+    pub lemma: Option<String>,
+    /// Part of speech tag of the stem, e.g. `"N"` for noun or `"V"` for verb.
+    pub pos: String,
+    /// Translation of the word, if loaded with [`Morphology::load_translations`].
+    pub translation: Option<String>,
+}
+
+/// Word-by-word morphology dataset, keyed by [`WordLocation`].
+///
+/// This type requires the `morphology` feature.
+///
+/// # Examples
+///
+/// ```
+/// use quranize::{Morphology, Quranize};
+///
+/// let corpus = "LOCATION\tFORM\tTAG\tFEATURES
+/// (1:1:1:1)\tbi\tP\tPREFIX|bi+
+/// (1:1:1:2)\tsomi\tN\tSTEM|POS:N|LEM:{som|ROOT:smw|M|GEN
+/// (1:1:2:1)\t{ll~ahi\tPN\tSTEM|POS:PN|LEM:{ll~ah|ROOT:Alh|GEN";
+/// let mut morphology = Morphology::load(corpus).unwrap();
+/// morphology.load_translations("(1:1:1)\tIn (the) name\n(1:1:2)\t(of) Allah").unwrap();
+///
+/// let q = Quranize::new();
+/// let location = q.get_word_location(q.find_sorted("اللَّهِ")[0]).unwrap();
+/// let analysis = morphology.get(location).unwrap();
+/// assert_eq!(analysis.root.as_deref(), Some("Alh"));
+/// assert_eq!(analysis.translation.as_deref(), Some("(of) Allah"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Morphology {
+    words: HashMap<WordLocation, WordAnalysis>,
+    roots: BTreeMap<String, Vec<WordLocation>>,
+}
+
+impl Morphology {
+    /// Load a morphology dataset in Quranic Arabic Corpus format, i.e. tab separated lines of
+    /// `(sura:aya:word:segment)`, segment form, tag, and `|` separated features.
+    /// Empty lines, comment lines starting with `#`, and the header line are skipped.
+    /// Returns the line number (starting from 1) of the first malformed line as an error.
+    pub fn load(corpus: &str) -> Result<Self, usize> {
+        let mut words = HashMap::<WordLocation, WordAnalysis>::new();
+        for (n, line) in { corpus.lines().enumerate() }.filter(|(_, l)| is_data_line(l)) {
+            let mut columns = line.split('\t');
+            let (Some(location), Some(form), Some(tag), features) = (
+                columns.next(),
+                columns.next(),
+                columns.next(),
+                columns.next().unwrap_or_default(),
+            ) else {
+                return Err(n + 1);
+            };
+            let location = parse_location(location, 4).ok_or(n + 1)?;
+            let word = words.entry(location).or_default();
+            word.form.push_str(form);
+            let features = Vec::from_iter(features.split('|'));
+            if features.contains(&"STEM") || word.pos.is_empty() {
+                word.pos = tag.to_string();
+            }
+            for feature in features {
+                match feature.split_once(':') {
+                    Some(("ROOT", root)) => word.root = Some(root.to_string()),
+                    Some(("LEM", lemma)) => word.lemma = Some(lemma.to_string()),
+                    _ => {}
+                }
+            }
+        }
+        let mut roots = BTreeMap::<String, Vec<WordLocation>>::new();
+        for (&location, word) in &words {
+            if let Some(root) = &word.root {
+                roots
+                    .entry(from_buckwalter(root))
+                    .or_default()
+                    .push(location);
+            }
+        }
+        roots
+            .values_mut()
+            .for_each(|locations| locations.sort_unstable());
+        Ok(Self { words, roots })
+    }
+
+    /// Load word translations from tab separated lines of `(sura:aya:word)` and translation,
+    /// for words already loaded by [`load`](Self::load). Translations of unknown words are
+    /// ignored. Returns the line number (starting from 1) of the first malformed line as an error.
+    pub fn load_translations(&mut self, translations: &str) -> Result<(), usize> {
+        for (n, line) in { translations.lines().enumerate() }.filter(|(_, l)| is_data_line(l)) {
+            let (location, translation) = line.split_once('\t').ok_or(n + 1)?;
+            let location = parse_location(location, 3).ok_or(n + 1)?;
+            if let Some(word) = self.words.get_mut(&location) {
+                word.translation = Some(translation.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the analysis of the word at `location`.
+    pub fn get(&self, location: WordLocation) -> Option<&WordAnalysis> {
+        self.words.get(&location)
+    }
+
+    /// Find words derived from `root`, given in Arabic letters (e.g. `"رحم"` or `"ر-ح-م"`) or
+    /// transliterated (e.g. `"r-h-m"`, read through the same tables as
+    /// [`Quranize::encode`](crate::Quranize::encode)), returning a list of tuple, sorted by the
+    /// first item:
+    /// - `String`: lemma in Arabic script, or the word form if the word has no lemma
+    /// - `usize`: number of words with the lemma above
+    /// - `Vec<WordLocation>`: locations of the words, sorted
+    ///
+    /// A transliterated root may match several Arabic roots, e.g. `"hmd"` matches both `"حمد"`
+    /// and `"همد"`. Words of all of them are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::Morphology;
+    ///
+    /// let corpus = "(1:1:3:1)\t{l\tDET\tPREFIX|Al+
+    /// (1:1:3:2)\tr~aHoma`ni\tADJ\tSTEM|POS:ADJ|LEM:r~aHoma`n|ROOT:rHm|MS|GEN
+    /// (1:1:4:1)\t{l\tDET\tPREFIX|Al+
+    /// (1:1:4:2)\tr~aHiymi\tADJ\tSTEM|POS:ADJ|LEM:r~aHiym|ROOT:rHm|MS|GEN
+    /// (1:3:1:1)\t{l\tDET\tPREFIX|Al+
+    /// (1:3:1:2)\tr~aHoma`ni\tADJ\tSTEM|POS:ADJ|LEM:r~aHoma`n|ROOT:rHm|MS|GEN";
+    /// let morphology = Morphology::load(corpus).unwrap();
+    /// let groups = morphology.find_root("ر-ح-م");
+    /// assert_eq!(groups[0], ("رَّحِيم".to_string(), 1, vec![(1, 1, 4)]));
+    /// assert_eq!(groups[1].1, 2);
+    /// assert_eq!(morphology.find_root("rahim"), groups);
+    /// ```
+    pub fn find_root(&self, root: &str) -> Vec<(String, usize, Vec<WordLocation>)> {
+        let mut groups = BTreeMap::<String, Vec<WordLocation>>::new();
+        let locations = { root_candidates(root).into_iter() }
+            .filter_map(|root| self.roots.get(&root))
+            .flatten();
+        for &location in locations {
+            let word = &self.words[&location];
+            let key = from_buckwalter(word.lemma.as_ref().unwrap_or(&word.form));
+            groups.entry(key).or_default().push(location);
+        }
+        { groups.into_iter() }
+            .map(|(lemma, mut locations)| {
+                locations.sort_unstable();
+                (lemma, locations.len(), locations)
+            })
+            .collect()
+    }
+
+    /// Get the number of loaded words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Check whether no word is loaded.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+const BUCKWALTER: [(char, char); 48] = [
+    ('\'', 'ء'),
+    ('|', 'آ'),
+    ('>', 'أ'),
+    ('&', 'ؤ'),
+    ('<', 'إ'),
+    ('}', 'ئ'),
+    ('A', 'ا'),
+    ('b', 'ب'),
+    ('p', 'ة'),
+    ('t', 'ت'),
+    ('v', 'ث'),
+    ('j', 'ج'),
+    ('H', 'ح'),
+    ('x', 'خ'),
+    ('d', 'د'),
+    ('*', 'ذ'),
+    ('r', 'ر'),
+    ('z', 'ز'),
+    ('s', 'س'),
+    ('$', 'ش'),
+    ('S', 'ص'),
+    ('D', 'ض'),
+    ('T', 'ط'),
+    ('Z', 'ظ'),
+    ('E', 'ع'),
+    ('g', 'غ'),
+    ('_', 'ـ'),
+    ('f', 'ف'),
+    ('q', 'ق'),
+    ('k', 'ك'),
+    ('l', 'ل'),
+    ('m', 'م'),
+    ('n', 'ن'),
+    ('h', 'ه'),
+    ('w', 'و'),
+    ('Y', 'ى'),
+    ('y', 'ي'),
+    ('F', 'ً'),
+    ('N', 'ٌ'),
+    ('K', 'ٍ'),
+    ('a', 'َ'),
+    ('u', 'ُ'),
+    ('i', 'ِ'),
+    ('~', 'ّ'),
+    ('o', 'ْ'),
+    ('^', 'ٓ'),
+    ('`', 'ٰ'),
+    ('{', 'ٱ'),
+];
+
+const MAX_ROOT_LEN: usize = 4;
+const ROOT_LETTERS: &str = "ءبتثجحخدذرزسشصضطظعغفقكلمنهوي";
+
+fn from_buckwalter(s: &str) -> String {
+    { s.chars() }
+        .map(|c| {
+            BUCKWALTER
+                .iter()
+                .find(|&&(b, _)| b == c)
+                .map_or(c, |&(_, a)| a)
+        })
+        .collect()
+}
+
+fn root_candidates(root: &str) -> Vec<String> {
+    match root.chars().any(|c| ('\u{0600}'..='\u{06FF}').contains(&c)) {
+        true => vec![root.chars().filter_map(normalize_root_letter).collect()],
+        false => {
+            let mut candidates = latin_root_candidates(&normalization::normalize(root));
+            candidates.sort_unstable();
+            candidates.dedup();
+            candidates
+        }
+    }
+}
+
+fn normalize_root_letter(c: char) -> Option<char> {
+    match c {
+        'آ' | 'أ' | 'ؤ' | 'إ' | 'ئ' => Some('ء'),
+        'ى' => Some('ي'),
+        c => ROOT_LETTERS.contains(c).then_some(c),
+    }
+}
+
+/// Find Arabic roots of at most `MAX_ROOT_LEN` letters readable as `s`, where vowels may be
+/// skipped. Each pair of remaining input and root read so far is only walked once.
+fn latin_root_candidates(s: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut walked = HashSet::new();
+    let mut stack = vec![(s, String::new())];
+    while let Some((s, root)) = stack.pop() {
+        if !walked.insert((s.len(), root.clone())) {
+            continue;
+        }
+        if s.is_empty() {
+            candidates.push(root);
+            continue;
+        }
+        if root.chars().count() < MAX_ROOT_LEN {
+            for c in ROOT_LETTERS.chars() {
+                let tsls = map(c).iter().filter(|tsl| !tsl.is_empty());
+                let rests = tsls.filter_map(|tsl| s.strip_prefix(tsl));
+                stack.extend(rests.map(|s| (s, format!("{root}{c}"))));
+            }
+        }
+        if let Some(s) = s.strip_prefix(['a', 'i', 'u', 'o', 'e']) {
+            stack.push((s, root));
+        }
+    }
+    candidates
+}
+
+fn is_data_line(line: &&str) -> bool {
+    !line.is_empty() && !line.starts_with('#') && !line.starts_with("LOCATION")
+}
+
+fn parse_location(location: &str, parts: usize) -> Option<WordLocation> {
+    let location = location.strip_prefix('(')?.strip_suffix(')')?;
+    let numbers = Vec::from_iter(location.split(':'));
+    match numbers[..] {
+        [s, a, w, ..] if numbers.len() == parts => {
+            Some((s.parse().ok()?, a.parse().ok()?, w.parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!(parse_location("(2:255:3:1)", 4), Some((2, 255, 3)));
+        assert_eq!(parse_location("(2:255:3)", 3), Some((2, 255, 3)));
+        assert_eq!(parse_location("(2:255:3)", 4), None);
+        assert_eq!(parse_location("2:255:3", 3), None);
+        assert_eq!(parse_location("(2:x:3)", 3), None);
+    }
+
+    #[test]
+    fn test_load() {
+        let corpus = "# comment\n\n\
+            (1:2:1:1)\t{l\tDET\tPREFIX|Al+\n\
+            (1:2:1:2)\tHamodu\tN\tSTEM|POS:N|LEM:Hamod|ROOT:Hmd|M|NOM\n\
+            (1:2:2:1)\tli\tP\tPREFIX|l:P+\n\
+            (1:2:2:2)\t~hi\tPN\tSTEM|POS:PN|LEM:{ll~ah|ROOT:Alh|GEN";
+        let mut m = Morphology::load(corpus).unwrap();
+        assert_eq!(m.len(), 2);
+        let expected = WordAnalysis {
+            form: "{lHamodu".to_string(),
+            root: Some("Hmd".to_string()),
+            lemma: Some("Hamod".to_string()),
+            pos: "N".to_string(),
+            translation: None,
+        };
+        assert_eq!(m.get((1, 2, 1)), Some(&expected));
+        assert_eq!(m.get((1, 2, 3)), None);
+
+        m.load_translations("(1:2:1)\tAll praises\n(9:9:9)\tunknown")
+            .unwrap();
+        assert_eq!(
+            m.get((1, 2, 1)).unwrap().translation.as_deref(),
+            Some("All praises")
+        );
+        assert_eq!(m.load_translations("(1:2:1)\n"), Err(1));
+        assert_eq!(Morphology::load("a\nb").unwrap_err(), 1);
+        assert_eq!(
+            Morphology::load("(1:1:1:1)\tbi\tP\n(1:1)\tx\tN").unwrap_err(),
+            2
+        );
+        assert!(Morphology::load("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_root_candidates() {
+        assert_eq!(root_candidates("ر-ح-م"), ["رحم"]);
+        assert_eq!(root_candidates("أَمِنَ"), ["ءمن"]);
+        let candidates = root_candidates("r-h-m");
+        assert!(candidates.contains(&"رحم".to_string()));
+        assert!(candidates.contains(&"رهم".to_string()));
+        assert!(root_candidates("rahima").contains(&"رحم".to_string()));
+        for long in [
+            "hhhhhhhhhhhhhhhhhhhh",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        ] {
+            let candidates = root_candidates(long);
+            assert!(candidates.iter().all(|r| r.chars().count() <= MAX_ROOT_LEN));
+        }
+        assert!(root_candidates("hhhhh").is_empty());
+        assert_eq!(from_buckwalter("{ll~ah"), "ٱللَّه");
+    }
+
+    #[test]
+    fn test_find_root() {
+        let corpus = "(2:1:1:1)\tkitaAbu\tN\tSTEM|POS:N|LEM:kitaAb|ROOT:ktb|M|NOM\n\
+            (2:1:2:1)\tkataba\tV\tSTEM|POS:V|PERF|LEM:kataba|ROOT:ktb|3MS\n\
+            (2:1:3:1)\tkitaAba\tN\tSTEM|POS:N|LEM:kitaAb|ROOT:ktb|M|ACC\n\
+            (2:1:4:1)\tqaAla\tV\tSTEM|POS:V|PERF|LEM:qaAla|ROOT:qwl|3MS";
+        let m = Morphology::load(corpus).unwrap();
+        let expected = [
+            ("كَتَبَ".to_string(), 1, vec![(2, 1, 2)]),
+            ("كِتَاب".to_string(), 2, vec![(2, 1, 1), (2, 1, 3)]),
+        ];
+        assert_eq!(m.find_root("كتب"), expected);
+        assert_eq!(m.find_root("k-t-b"), expected);
+        assert_eq!(m.find_root("qwl")[0].2, [(2, 1, 4)]);
+        assert!(m.find_root("xyz").is_empty());
+        assert!(m.find_root("").is_empty());
+    }
+}
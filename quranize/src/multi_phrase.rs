@@ -0,0 +1,163 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use crate::{
+    Quranize,
+    suffix_tree::{is_separator, word_count},
+};
+
+/// Options for [`Quranize::find_phrases`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhraseQuery {
+    /// Require the phrases to appear in an aya in the same order as they are given.
+    pub ordered: bool,
+    /// Maximum number of words allowed between two consecutive phrases in an aya.
+    pub max_distance: Option<usize>,
+}
+
+type Spans = BTreeMap<usize, Vec<Range<usize>>>;
+
+impl Quranize {
+    /// Find ayas containing all transliterated `phrases`, returning a list of tuple:
+    /// - `usize`: aya row / aya offset (`0..6236`)
+    /// - `Vec<Range<usize>>`: byte range of each phrase in the aya text, in the same order as
+    ///   `phrases`, extended to the end of the last matched word
+    ///
+    /// Each phrase is encoded independently, so any of its quran forms may match.
+    /// Matched phrases never overlap each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{PhraseQuery, Quranize};
+    ///
+    /// let q = Quranize::new();
+    /// let query = PhraseQuery { ordered: true, ..Default::default() };
+    /// let results = q.find_phrases(&["ya ayyuhalladzina amanu", "kutiba alaikumus siyam"], query);
+    /// let (i, spans) = &results[0];
+    /// let &(_, sura, aya, text) = q.get_data(*i).unwrap();
+    /// assert_eq!((sura, aya), (2, 183));
+    /// assert_eq!(&text[spans[1].clone()], "كُتِبَ عَلَيْكُمُ الصِّيَامُ");
+    /// ```
+    pub fn find_phrases(
+        &self,
+        phrases: &[&str],
+        query: PhraseQuery,
+    ) -> Vec<(usize, Vec<Range<usize>>)> {
+        let spans_list = Vec::from_iter(phrases.iter().map(|p| self.phrase_spans(p)));
+        let Some((first_spans, other_spans_list)) = spans_list.split_first() else {
+            return vec![];
+        };
+        { first_spans.keys() }
+            .filter(|i| other_spans_list.iter().all(|spans| spans.contains_key(i)))
+            .filter_map(|&i| {
+                let candidates = Vec::from_iter(spans_list.iter().map(|spans| &spans[&i][..]));
+                let mut chosen = Vec::with_capacity(candidates.len());
+                let found = self.arrange_phrases(self.data[i].3, &candidates, query, &mut chosen);
+                found.then_some((i, chosen))
+            })
+            .collect()
+    }
+
+    fn phrase_spans(&self, phrase: &str) -> Spans {
+        let mut spans = Spans::new();
+        for (quran, _, _) in self.encode(phrase) {
+            for (i, j) in self.find(&quran) {
+                let text = self.data[i].3;
+                let rest = text.get(j + quran.len()..).unwrap_or_default();
+                let k = j + quran.len() + rest.split(is_separator).next().map_or(0, str::len);
+                spans.entry(i).or_default().push(j..k);
+            }
+        }
+        spans.values_mut().for_each(|s| {
+            s.sort_unstable_by_key(|r| (r.start, r.end));
+            s.dedup();
+        });
+        spans
+    }
+
+    fn arrange_phrases(
+        &self,
+        text: &str,
+        candidates: &[&[Range<usize>]],
+        query: PhraseQuery,
+        chosen: &mut Vec<Range<usize>>,
+    ) -> bool {
+        let Some(spans) = candidates.get(chosen.len()) else {
+            let mut sorted = chosen.clone();
+            sorted.sort_unstable_by_key(|r| r.start);
+            return { sorted.windows(2) }.all(|w| {
+                query
+                    .max_distance
+                    .is_none_or(|d| word_count(&text[w[0].end..w[1].start]) <= d)
+            });
+        };
+        for span in spans.iter() {
+            let fits = match query.ordered {
+                true => chosen.last().is_none_or(|prev| prev.end <= span.start),
+                false => { chosen.iter() }.all(|c| c.end <= span.start || span.end <= c.start),
+            };
+            if fits {
+                chosen.push(span.clone());
+                if self.arrange_phrases(text, candidates, query, chosen) {
+                    return true;
+                }
+                chosen.pop();
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_phrases() {
+        let q = Quranize::new();
+        let phrases = ["ya ayyuhalladzina amanu", "kutiba alaikum"];
+        let ayas = |query| {
+            { q.find_phrases(&phrases, query).into_iter() }
+                .map(|(i, _)| q.get_data(i).map(|&(_, s, a, _)| (s, a)).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let ordered = PhraseQuery {
+            ordered: true,
+            ..Default::default()
+        };
+        assert_eq!(ayas(ordered), [(2, 178), (2, 183)]);
+
+        let phrases = ["ya ayyuhalladzina amanu", "kama kutiba"];
+        let found = |max_distance| {
+            let query = PhraseQuery {
+                max_distance,
+                ..ordered
+            };
+            !q.find_phrases(&phrases, query).is_empty()
+        };
+        assert!(found(Some(3)));
+        assert!(!found(Some(2)));
+
+        let phrases = ["kutiba alaikum", "ya ayyuhalladzina amanu"];
+        let ayas = |query| q.find_phrases(&phrases, query).len();
+        assert_eq!(ayas(ordered), 0);
+        assert_eq!(ayas(Default::default()), 2);
+
+        assert!(q.find_phrases(&[], Default::default()).is_empty());
+        assert!(
+            q.find_phrases(&["bismillah", "abcd"], Default::default())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_find_phrases_without_overlap() {
+        let q = Quranize::new();
+        let results = q.find_phrases(&["qul", "qul"], Default::default());
+        let (i, spans) = &results[0];
+        let text = q.get_data(*i).unwrap().3;
+        assert!(spans[0].end <= spans[1].start || spans[1].end <= spans[0].start);
+        assert!(text[spans[0].clone()].starts_with("قُل"));
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    iter::once,
+};
+
+use crate::{
+    Quranize,
+    suffix_tree::{Index, is_separator, word_count},
+};
+
+impl Quranize {
+    /// List maximal repeated phrases (mutashabihat) of at least `min_words` words, returning a
+    /// list of tuple, sorted from the longest phrase:
+    /// - `String`: the repeated phrase
+    /// - `Vec<Index>`: every location of the phrase, sorted in mushaf order
+    ///
+    /// A phrase is maximal when its locations are not all preceded, nor all followed, by the same
+    /// word, i.e. it cannot be extended without losing some of its locations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let phrases = q.repeated_phrases(4);
+    /// let (phrase, locations) = phrases.iter().find(|(_, l)| l.len() == 31).unwrap();
+    /// assert_eq!(phrase, "فَبِأَيِّ آلَاءِ رَبِّكُمَا تُكَذِّبَانِ");
+    /// assert_eq!(locations[0], (4913, 0));
+    /// ```
+    pub fn repeated_phrases(&self, min_words: usize) -> Vec<(String, Vec<Index>)> {
+        let mut phrases = BTreeMap::new();
+        self.collect_repeated_phrases(0, &mut String::new(), min_words, &mut phrases);
+        let mut phrases = Vec::from_iter(
+            { phrases.into_iter() }
+                .map(|(p, l)| (p, Vec::from_iter(l)))
+                .filter(|(p, l)| self.is_maximal_phrase(p, l)),
+        );
+        phrases.sort_by_key(|(p, l)| (Reverse(word_count(p)), Reverse(l.len())));
+        phrases
+    }
+
+    fn collect_repeated_phrases(
+        &self,
+        v: usize,
+        path: &mut String,
+        min_words: usize,
+        phrases: &mut BTreeMap<String, BTreeSet<Index>>,
+    ) {
+        for &(_, w, l) in self.tree.edges_from(v) {
+            if self.tree.count_data(w) < 2 {
+                continue;
+            }
+            for (k, _) in l.char_indices().filter(|&(_, c)| is_separator(c)) {
+                let phrase = format!("{path}{}", &l[..k]);
+                let phrase = phrase.trim_end_matches(is_separator);
+                if word_count(phrase) >= min_words.max(1) {
+                    let locations = phrases.entry(phrase.to_string()).or_default();
+                    locations.extend(self.tree.collect_data(w));
+                }
+            }
+            let len = path.len();
+            path.push_str(l);
+            self.collect_repeated_phrases(w, path, min_words, phrases);
+            path.truncate(len);
+        }
+    }
+
+    fn is_maximal_phrase(&self, phrase: &str, locations: &[Index]) -> bool {
+        let texts = Vec::from_iter(locations.iter().map(|&(i, j)| (self.data[i].3, j)));
+        let prevs = Vec::from_iter(texts.iter().map(|&(t, j)| prev_word(t, j)));
+        let nexts = Vec::from_iter(texts.iter().map(|&(t, j)| next_word(t, j + phrase.len())));
+        let extendable =
+            |words: &[&str]| !words[0].is_empty() && words.iter().all(|w| w == &words[0]);
+        locations.len() > 1 && !extendable(&prevs) && !extendable(&nexts)
+    }
+
+    /// List ayas sharing a phrase of at least `min_words` words with aya row `i` (`0..6236`),
+    /// returning a list of tuple, ranked by the shared length:
+    /// - `usize`: the other aya row / aya offset
+    /// - `usize`: word count of the longest shared phrase
+    /// - `String`: the longest shared phrase
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let similar_ayas = q.similar_ayas(4913, 3);
+    /// assert_eq!(similar_ayas.len(), 30);
+    /// assert_eq!(similar_ayas[0], (4916, 4, "فَبِأَيِّ آلَاءِ رَبِّكُمَا تُكَذِّبَانِ".to_string()));
+    /// ```
+    pub fn similar_ayas(&self, i: usize, min_words: usize) -> Vec<(usize, usize, String)> {
+        let Some(&(_, _, _, text)) = self.data.get(i) else {
+            return vec![];
+        };
+        let is_word_start = |&(j, c): &(usize, char)| {
+            !is_separator(c) && (j == 0 || text[..j].ends_with(is_separator))
+        };
+        let is_word_end =
+            |&(k, c): &(usize, char)| is_separator(c) && !text[..k].ends_with(is_separator);
+        let ends = Vec::from_iter(
+            text.char_indices()
+                .filter(is_word_end)
+                .map(|(k, _)| k)
+                .chain(once(text.len())),
+        );
+        let mut shared = BTreeMap::<usize, (usize, &str)>::new();
+        for (j, _) in text.char_indices().filter(is_word_start) {
+            for (words, &k) in ends.iter().skip_while(|&&k| k <= j).enumerate() {
+                let words = words + 1;
+                let phrase = &text[j..k];
+                let locations = Vec::from_iter(self.find(phrase).into_iter().filter(|&(x, y)| {
+                    let rest = &self.data[x].3[y + phrase.len()..];
+                    (x, y) != (i, j) && rest.chars().next().is_none_or(is_separator)
+                }));
+                if locations.is_empty() {
+                    break;
+                }
+                if words < min_words {
+                    continue;
+                }
+                for (x, _) in locations.into_iter().filter(|&(x, _)| x != i) {
+                    let entry = shared.entry(x).or_default();
+                    if words > entry.0 {
+                        *entry = (words, phrase);
+                    }
+                }
+            }
+        }
+        let mut similar_ayas =
+            Vec::from_iter(shared.into_iter().map(|(x, (n, p))| (x, n, p.to_string())));
+        similar_ayas.sort_by_key(|&(x, n, _)| (Reverse(n), x));
+        similar_ayas
+    }
+}
+
+fn prev_word(text: &str, j: usize) -> &str {
+    let end = text[..j].trim_end_matches(is_separator).len();
+    let start = text[..end].trim_end_matches(|c| !is_separator(c)).len();
+    &text[start..j]
+}
+
+fn next_word(text: &str, j: usize) -> &str {
+    let start = text.len() - text[j..].trim_start_matches(is_separator).len();
+    let end = text[start..]
+        .find(is_separator)
+        .map_or(text.len(), |k| start + k);
+    &text[j..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_neighbor_words() {
+        let text = "ab cd ۖ ef gh";
+        assert_eq!(prev_word(text, 0), "");
+        assert_eq!(prev_word(text, 3), "ab ");
+        assert_eq!(prev_word(text, 12), "ef ");
+        assert_eq!(prev_word(text, 9), "cd ۖ ");
+        assert_eq!(next_word(text, 2), " cd");
+        assert_eq!(next_word(text, 5), " ۖ ef");
+        assert_eq!(next_word(text, 14), "");
+    }
+
+    #[test]
+    fn test_repeated_phrases() {
+        let q = Quranize::new();
+        let phrases = q.repeated_phrases(4);
+        assert!(
+            phrases
+                .iter()
+                .all(|(p, l)| word_count(p) >= 4 && l.len() > 1)
+        );
+        assert!(
+            phrases
+                .windows(2)
+                .all(|w| word_count(&w[0].0) >= word_count(&w[1].0))
+        );
+        for (phrase, locations) in &phrases {
+            for &(i, j) in locations {
+                assert!(q.data[i].3[j..].starts_with(phrase.as_str()));
+            }
+        }
+        let basmalah = phrases
+            .iter()
+            .find(|(p, _)| p.ends_with("الرَّحِيمِ") && p.starts_with("بِسْمِ"));
+        assert_eq!(
+            basmalah.map(|(_, l)| l.as_slice()),
+            Some(&[(0, 0), (3188, 64)][..])
+        );
+    }
+
+    #[test]
+    fn test_similar_ayas() {
+        let q = Quranize::new();
+        let similar_ayas = q.similar_ayas(0, 4);
+        assert_eq!(similar_ayas.len(), 1);
+        assert_eq!(similar_ayas[0].0, 3188);
+        assert_eq!(similar_ayas[0].1, 4);
+        assert!(q.similar_ayas(0, 5).is_empty());
+        assert!(q.similar_ayas(6236, 1).is_empty());
+    }
+}
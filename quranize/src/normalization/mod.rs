@@ -1,19 +1,38 @@
+use std::ops::Range;
+
 pub(super) fn normalize(text: &str) -> String {
-    text.chars()
-        .filter_map(|c| match c.to_ascii_lowercase() {
-            c @ ('a'..='z' | '\'') => Some(c),
+    normalize_with_ranges(text).0
+}
+
+pub(super) fn normalize_with_ranges(text: &str) -> (String, Vec<Range<usize>>) {
+    text.char_indices()
+        .filter_map(|(i, c)| match c.to_ascii_lowercase() {
+            c @ ('a'..='z' | '\'') => Some((c, i..i + 1)),
             _ => None,
         })
-        .collect()
+        .unzip()
 }
 
 pub(super) fn normalize_muqottoah(text: &str) -> String {
-    let mut chars = Vec::from_iter(text.chars().filter_map(|c| match c.to_ascii_lowercase() {
-        c @ ('a'..='z' | '\'' | ' ') => Some(c),
-        _ => None,
-    }));
-    chars.dedup_by(|&mut a, &mut b| a == b && (a == 'a' || a == 'o' || a == 'i' || a == 'u'));
-    chars.into_iter().filter(|&c| c != ' ').collect()
+    normalize_muqottoah_with_ranges(text).0
+}
+
+pub(super) fn normalize_muqottoah_with_ranges(text: &str) -> (String, Vec<Range<usize>>) {
+    let chars = text
+        .char_indices()
+        .filter_map(|(i, c)| match c.to_ascii_lowercase() {
+            c @ ('a'..='z' | '\'' | ' ') => Some((c, i..i + 1)),
+            _ => None,
+        });
+    let mut chars = Vec::from_iter(chars);
+    chars.dedup_by(|(a, r), (b, s)| {
+        let is_dup = a == b && (*a == 'a' || *a == 'o' || *a == 'i' || *a == 'u');
+        if is_dup {
+            s.end = r.end;
+        }
+        is_dup
+    });
+    chars.into_iter().filter(|&(c, _)| c != ' ').unzip()
 }
 
 #[cfg(test)]
@@ -50,4 +69,23 @@ mod tests {
             normalize_muqottoah("kaaaf haa yaa 'aiiin shoood"),
         );
     }
+
+    #[test]
+    fn test_normalize_with_ranges() {
+        let (s, ranges) = normalize_with_ranges("Qul A'udzu");
+        assert_eq!(s, "qula'udzu");
+        assert_eq!(
+            ranges,
+            [0..1, 1..2, 2..3, 4..5, 5..6, 6..7, 7..8, 8..9, 9..10]
+        );
+        let (s, ranges) = normalize_with_ranges("Bi-smi?");
+        assert_eq!(
+            (s.as_str(), ranges),
+            ("bismi", vec![0..1, 1..2, 3..4, 4..5, 5..6])
+        );
+
+        let (s, ranges) = normalize_muqottoah_with_ranges("Kaaaf haa");
+        assert_eq!(s, "kafha");
+        assert_eq!(ranges, [0..1, 1..4, 4..5, 6..7, 7..9]);
+    }
 }
@@ -0,0 +1,158 @@
+use crate::{
+    SearchScope,
+    quran_metadata::{AYA_COUNT, PAGE_OFFSETS, SURA_STARTS},
+    words::aya_row,
+};
+
+/// Page layout of a mushaf, i.e. the aya each page starts with, for
+/// [`Quranize::with_page_layout`](crate::Quranize::with_page_layout).
+/// The default is the 604-page Madinah mushaf, see [`madinah`](Self::madinah), which is the
+/// only layout bundled with this crate. Layouts of other mushafs, e.g. 13-line or 16-line
+/// Indo-Pak mushafs, are loaded from a dataset with [`parse`](Self::parse).
+///
+/// # Examples
+///
+/// ```
+/// use quranize::PageLayout;
+///
+/// let layout = PageLayout::parse("1:1\n2:1\n2:6").unwrap();
+/// assert_eq!(layout.page_count(), 3);
+/// assert_eq!((layout.page(6), layout.page(7)), (Some(1), Some(2)));
+/// assert_eq!(layout.page_start(3), Some((2, 6)));
+/// assert_eq!(PageLayout::madinah().page_count(), 604);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageLayout {
+    starts: Vec<usize>,
+}
+
+impl Default for PageLayout {
+    fn default() -> Self {
+        Self::madinah()
+    }
+}
+
+impl PageLayout {
+    /// Create the page layout of the 15-line Madinah mushaf with 604 pages.
+    pub fn madinah() -> Self {
+        let starts = PAGE_OFFSETS.map(|(s, a)| SURA_STARTS[s - 1] + a - 1);
+        Self {
+            starts: starts.to_vec(),
+        }
+    }
+
+    /// Create a page layout from the sura number and aya number each page starts with, in page
+    /// order. Returns `None` if the first page does not start with aya 1:1, an aya does not
+    /// exist, or the ayas are not strictly increasing.
+    pub fn new(starts: impl IntoIterator<Item = (u8, u16)>) -> Option<Self> {
+        let mut layout = Self { starts: vec![] };
+        for (sura, aya) in starts {
+            layout.push(sura, aya)?;
+        }
+        (!layout.starts.is_empty()).then_some(layout)
+    }
+
+    /// Parse a page layout from lines of `sura:aya` (or `sura,aya`), one line per page in page
+    /// order, e.g. a 13-line or 16-line Indo-Pak mushaf layout. Empty lines and comment lines
+    /// starting with `#` are skipped. A page starting in the middle of an aya is given as the
+    /// next aya starting on it.
+    /// Returns the line number (starting from 1) of the first invalid line as an error, see
+    /// [`new`](Self::new) for the validity.
+    pub fn parse(text: &str) -> Result<Self, usize> {
+        let mut layout = Self { starts: vec![] };
+        let mut line_count = 0;
+        for (n, line) in text.lines().enumerate() {
+            line_count = n + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (sura, aya) = line.split_once([':', ',']).ok_or(n + 1)?;
+            let (sura, aya) = (sura.trim().parse().ok(), aya.trim().parse().ok());
+            layout
+                .push(sura.ok_or(n + 1)?, aya.ok_or(n + 1)?)
+                .ok_or(n + 1)?;
+        }
+        match layout.starts.is_empty() {
+            true => Err(line_count + 1),
+            false => Ok(layout),
+        }
+    }
+
+    fn push(&mut self, sura: u8, aya: u16) -> Option<()> {
+        let i = aya_row(sura, aya)?;
+        match self.starts.last() {
+            None if i != 0 => None,
+            Some(&last) if i <= last => None,
+            _ => {
+                self.starts.push(i);
+                Some(())
+            }
+        }
+    }
+
+    /// Get the number of pages.
+    pub fn page_count(&self) -> u16 {
+        self.starts.len() as u16
+    }
+
+    /// Get the page number (starting from 1) of aya row `i` (`0..6236`).
+    pub fn page(&self, i: usize) -> Option<u16> {
+        (i < AYA_COUNT).then(|| self.starts.partition_point(|&j| j <= i) as u16)
+    }
+
+    /// Get the sura number and aya number page number `page` starts with.
+    pub fn page_start(&self, page: u16) -> Option<(u8, u16)> {
+        let i = *self.starts.get((page as usize).checked_sub(1)?)?;
+        let sura = SURA_STARTS.partition_point(|&j| j <= i);
+        Some((sura as u8, (i - SURA_STARTS[sura - 1] + 1) as u16))
+    }
+
+    /// Create a scope covering page number `page`. Other numbers give an empty scope.
+    pub fn scope(&self, page: u16) -> SearchScope {
+        SearchScope::new(SearchScope::nth_range(&self.starts, page as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let text = "# page starts\n1:1\n\n2, 1\n2:6\n";
+        let layout = PageLayout::parse(text).unwrap();
+        assert_eq!(layout, PageLayout::new([(1, 1), (2, 1), (2, 6)]).unwrap());
+        assert_eq!(layout.page(0), Some(1));
+        assert_eq!(layout.page(12), Some(3));
+        assert_eq!(layout.page(AYA_COUNT), None);
+        assert_eq!(layout.scope(2), SearchScope::new(Some(7..12)));
+        assert_eq!(layout.scope(3), SearchScope::new(Some(12..AYA_COUNT)));
+        assert_eq!(layout.page_start(4), None);
+
+        assert_eq!(PageLayout::parse("1:2\n2:1"), Err(1));
+        assert_eq!(PageLayout::parse("1:1\n2:1\n1:7"), Err(3));
+        assert_eq!(PageLayout::parse("1:1\n2:1\n2:1"), Err(3));
+        assert_eq!(PageLayout::parse("1:1\n2:287"), Err(2));
+        assert_eq!(PageLayout::parse("1:1\n2"), Err(2));
+        assert_eq!(PageLayout::parse("# empty\n"), Err(2));
+        assert_eq!(PageLayout::new([]), None);
+    }
+
+    #[test]
+    fn test_madinah() {
+        let layout = PageLayout::default();
+        assert_eq!(layout.page_start(582), Some((78, 1)));
+        assert_eq!(layout.page(5672), Some(582));
+        assert_eq!(layout.page(AYA_COUNT - 1), Some(604));
+        let text = String::from_iter((1..=604).map(|p| {
+            let (s, a) = layout.page_start(p).unwrap();
+            format!("{s}:{a}\n")
+        }));
+        assert_eq!(PageLayout::parse(&text), Ok(layout));
+        for page in [1, 2, 604, 605] {
+            assert_eq!(PageLayout::madinah().scope(page), SearchScope::page(page));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{Quranize, suffix_tree::Index};
+
+impl Quranize {
+    /// Find `s` in Alquran like [`find`](Self::find), but sorted in mushaf order,
+    /// i.e. by aya row and then by string offset.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let locations = q.find_sorted("الرَّحْمَٰنِ الرَّحِيمِ");
+    /// assert_eq!(locations[..2], [(0, 28), (2, 0)]);
+    /// assert!(locations.is_sorted());
+    /// ```
+    pub fn find_sorted(&self, s: &str) -> Vec<Index> {
+        let mut locations = Vec::from_iter(self.find_iter(s));
+        locations.sort_unstable();
+        locations
+    }
+
+    /// Get at most `limit` locations of `s` in mushaf order, skipping the first `offset` ones.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let locations = q.find_sorted("الرَّحْمَٰنِ الرَّحِيمِ");
+    /// assert_eq!(q.find_paginated("الرَّحْمَٰنِ الرَّحِيمِ", 1, 2), locations[1..3]);
+    /// ```
+    pub fn find_paginated(&self, s: &str, offset: usize, limit: usize) -> Vec<Index> {
+        { self.tree.find_sorted_iter(s, 0, None) }
+            .skip(offset)
+            .take(limit)
+            .collect()
+    }
+
+    /// Get at most `limit` locations of `s` in mushaf order, coming after `cursor`, returning:
+    /// - `Vec<Index>`: the locations of this page
+    /// - `Option<String>`: cursor for the next page, or `None` if this is the last page
+    ///
+    /// A `cursor` of `None` starts from the beginning. Ayas before the cursor are pruned from the
+    /// search, and the search stops right after the locations of this page, so later pages are
+    /// cheaper. Like [`find_paginated`](Self::find_paginated), a `limit` of 0 gives an empty page,
+    /// returned with `cursor` unchanged. Returns `None` only if `cursor` is not a valid cursor.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (first_page, cursor) = q.find_after("رَبِّ الْعَالَمِينَ", None, 10).unwrap();
+    /// assert_eq!(first_page.len(), 10);
+    /// let (second_page, _) = q.find_after("رَبِّ الْعَالَمِينَ", cursor.as_deref(), 10).unwrap();
+    /// assert!(first_page.last() < second_page.first());
+    /// assert_eq!(q.find_after("رَبِّ", Some("x"), 10), None);
+    /// ```
+    pub fn find_after(
+        &self,
+        s: &str,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Option<(Vec<Index>, Option<String>)> {
+        let after = match cursor {
+            Some(cursor) => Some(parse_cursor(cursor)?),
+            None => None,
+        };
+        if limit == 0 {
+            return Some((vec![], cursor.map(String::from)));
+        }
+        let locations = self.tree.find_sorted_iter(s, 0, after);
+        let mut page = Vec::from_iter(locations.take(limit.saturating_add(1)));
+        let has_more = page.len() > limit;
+        page.truncate(limit);
+        let next_cursor = { page.last() }
+            .filter(|_| has_more)
+            .map(|&(i, j)| format!("{i}:{j}"));
+        Some((page, next_cursor))
+    }
+}
+
+fn parse_cursor(cursor: &str) -> Option<Index> {
+    let (i, j) = cursor.split_once(':')?;
+    Some((i.parse().ok()?, j.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_cursor() {
+        assert_eq!(parse_cursor("12:345"), Some((12, 345)));
+        assert_eq!(parse_cursor("12"), None);
+        assert_eq!(parse_cursor("a:1"), None);
+        assert_eq!(parse_cursor(""), None);
+    }
+
+    #[test]
+    fn test_find_after() {
+        let q = Quranize::new();
+        let s = "مِنْ";
+        let expected = q.find_sorted(s);
+        let mut locations = vec![];
+        let mut cursor = None;
+        loop {
+            let (page, next_cursor) = q.find_after(s, cursor.as_deref(), 100).unwrap();
+            assert!(page.len() <= 100);
+            locations.extend(page);
+            cursor = next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(locations, expected);
+        assert_eq!(q.find_after(s, Some("6235:0"), 100), Some((vec![], None)));
+        assert_eq!(q.find_after("abc", None, 10), Some((vec![], None)));
+        assert_eq!(
+            q.find_after(s, Some("0:0"), 0),
+            Some((vec![], Some("0:0".to_string())))
+        );
+        assert_eq!(q.find_after(s, None, 0), Some((vec![], None)));
+        assert_eq!(q.find_after(s, Some("x"), 0), None);
+        let (page, cursor) = q.find_after(s, None, 1).unwrap();
+        let (i, j) = expected[0];
+        assert_eq!((page, cursor), (vec![(i, j)], Some(format!("{i}:{j}"))));
+    }
+
+    #[test]
+    fn test_find_paginated() {
+        let q = Quranize::new();
+        let s = "اللَّهِ";
+        let locations = q.find_sorted(s);
+        assert_eq!(q.find_paginated(s, 0, 5), locations[..5]);
+        assert_eq!(q.find_paginated(s, 5, 5), locations[5..10]);
+        assert!(q.find_paginated(s, locations.len(), 5).is_empty());
+        assert_eq!(q.find_paginated(s, 0, usize::MAX), locations);
+        assert_eq!(q.find_paginated(s, usize::MAX, 5), []);
+        assert_eq!(
+            q.find_paginated("الرَّحْمَٰنِ الرَّحِيمِ", 0, 100),
+            q.find_sorted("الرَّحْمَٰنِ الرَّحِيمِ")
+        );
+    }
+}
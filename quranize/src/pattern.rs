@@ -0,0 +1,156 @@
+use std::{collections::BTreeSet, ops::Range};
+
+use crate::{Quranize, words::words_with_ranges};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Word(&'a str),
+    Gap(usize, usize),
+}
+
+impl Quranize {
+    /// Find a word pattern in Alquran, returning a list of tuple:
+    /// - `usize`: aya row / aya offset (`0..6236`)
+    /// - `Range<usize>`: byte range of the matched text in the aya, including the gaps
+    ///
+    /// The pattern is a list of space-separated tokens, each of them is either:
+    /// - a word, matching exactly the same word
+    /// - `*`, matching any single word
+    /// - `*{n}`, matching any `n` words
+    /// - `*{m,n}`, matching any `m` to `n` words, preferring the shortest gap
+    ///
+    /// A match spans at least one word, so a gap that would match nothing is lengthened if it can
+    /// be, e.g. `"*{0,2}"` matches every single word. Pause marks between words are skipped.
+    /// Returns `None` if the pattern contains an invalid gap token.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let locations = q.find_pattern("إِنَّ اللَّهَ *{1,2} رَّحِيمٌ").unwrap();
+    /// let (i, span) = locations[0].clone();
+    /// let &(_, sura, aya, text) = q.get_data(i).unwrap();
+    /// assert_eq!((sura, aya), (2, 143));
+    /// assert_eq!(&text[span], "إِنَّ اللَّهَ بِالنَّاسِ لَرَءُوفٌ رَّحِيمٌ");
+    /// assert_eq!(q.find_pattern("إِنَّ *{x}"), None);
+    /// ```
+    pub fn find_pattern(&self, pattern: &str) -> Option<Vec<(usize, Range<usize>)>> {
+        let tokens = parse_pattern(pattern)?;
+        let longest_word = { tokens.iter() }
+            .filter_map(|t| match t {
+                Token::Word(w) => Some(*w),
+                Token::Gap(..) => None,
+            })
+            .max_by_key(|w| w.len());
+        let ayas = match longest_word {
+            Some(w) => BTreeSet::from_iter(self.find(w).into_iter().map(|(i, _)| i)),
+            None => BTreeSet::from_iter(0..self.data.len()),
+        };
+        let locations = ayas.into_iter().flat_map(|i| {
+            let words = words_with_ranges(self.data[i].3);
+            let ends = (0..words.len()).map(|k| (k, match_tokens(&tokens, &words, k, k + 1)));
+            let spans = ends.filter_map(|(k, end)| Some(words[k].0.start..words[end? - 1].0.end));
+            Vec::from_iter(spans.map(|span| (i, span)))
+        });
+        Some(locations.collect())
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Option<Vec<Token<'_>>> {
+    { pattern.split_whitespace() }
+        .map(|t| match t.strip_prefix('*') {
+            None => Some(Token::Word(t)),
+            Some("") => Some(Token::Gap(1, 1)),
+            Some(bounds) => {
+                let bounds = bounds.strip_prefix('{')?.strip_suffix('}')?;
+                let (m, n) = bounds.split_once(',').unwrap_or((bounds, bounds));
+                let (m, n) = (m.trim().parse().ok()?, n.trim().parse().ok()?);
+                (m <= n).then_some(Token::Gap(m, n))
+            }
+        })
+        .collect()
+}
+
+/// Match `tokens` against `words` from word `k`, returning the end of the match, which is at
+/// least `min_end`.
+fn match_tokens(
+    tokens: &[Token],
+    words: &[(Range<usize>, &str)],
+    k: usize,
+    min_end: usize,
+) -> Option<usize> {
+    match tokens.split_first() {
+        None => (k >= min_end).then_some(k),
+        Some((Token::Word(w), tokens)) => match words.get(k) {
+            Some((_, word)) if word == w => match_tokens(tokens, words, k + 1, min_end),
+            _ => None,
+        },
+        Some((&Token::Gap(m, n), tokens)) => {
+            (m..=n.min(words.len() - k)).find_map(|g| match_tokens(tokens, words, k + g, min_end))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(parse_pattern(""), Some(vec![]));
+        assert_eq!(
+            parse_pattern("a * b *{2} c *{0,3}"),
+            Some(vec![
+                Token::Word("a"),
+                Token::Gap(1, 1),
+                Token::Word("b"),
+                Token::Gap(2, 2),
+                Token::Word("c"),
+                Token::Gap(0, 3),
+            ])
+        );
+        assert_eq!(parse_pattern("a *{3,1}"), None);
+        assert_eq!(parse_pattern("a *{1"), None);
+        assert_eq!(parse_pattern("a *x"), None);
+    }
+
+    #[test]
+    fn test_match_tokens() {
+        let words = words_with_ranges("a b c d");
+        let tokens = parse_pattern("a *{0,3} d").unwrap();
+        assert_eq!(match_tokens(&tokens, &words, 0, 1), Some(4));
+        assert_eq!(match_tokens(&tokens, &words, 1, 2), None);
+        let tokens = parse_pattern("b * *{0,5}").unwrap();
+        assert_eq!(match_tokens(&tokens, &words, 1, 2), Some(3));
+        assert_eq!(match_tokens(&tokens, &words, 1, 4), Some(4));
+        let tokens = parse_pattern("*{0,5}").unwrap();
+        assert_eq!(match_tokens(&tokens, &words, 1, 1), Some(1));
+        assert_eq!(match_tokens(&tokens, &words, 1, 2), Some(2));
+        assert_eq!(match_tokens(&tokens, &words, 4, 5), None);
+        let tokens = parse_pattern("a *{0,18446744073709551615} d").unwrap();
+        assert_eq!(match_tokens(&tokens, &words, 0, 1), Some(4));
+        let tokens = parse_pattern("c *{18446744073709551615}").unwrap();
+        assert_eq!(match_tokens(&tokens, &words, 2, 3), None);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let q = Quranize::new();
+        let ayas = |p| {
+            let locations = q.find_pattern(p).unwrap().into_iter();
+            Vec::from_iter(
+                locations.map(|(i, _)| q.get_data(i).map(|&(_, s, a, _)| (s, a)).unwrap()),
+            )
+        };
+        assert_eq!(ayas("يَا أَيُّهَا الَّذِينَ آمَنُوا *{2} الصِّيَامُ"), [(2, 183)]);
+        assert_eq!(ayas("يَا أَيُّهَا الَّذِينَ آمَنُوا * الصِّيَامُ"), []);
+        assert_eq!(ayas("فَبِأَيِّ آلَاءِ * تُكَذِّبَانِ").len(), 31);
+        assert_eq!(ayas("abc *").len(), 0);
+        assert_eq!(ayas("*{0,5}").len(), ayas("*").len());
+        assert_eq!(ayas("*{0}").len(), 0);
+        assert_eq!(ayas("").len(), 0);
+
+        let (i, span) = q.find_pattern("* الرَّحِيمِ").unwrap().remove(0);
+        assert_eq!((i, &q.data[i].3[span]), (0, "الرَّحْمَٰنِ الرَّحِيمِ"));
+    }
+}
@@ -11,6 +11,29 @@ pub(super) const SURA_STARTS: [usize; 114] = [
     6230,
 ];
 
+pub(super) const JUZ_STARTS: [usize; 30] = [
+    0, 148, 259, 385, 516, 640, 750, 899, 1041, 1200, 1327, 1478, 1648, 1802, 2029, 2214, 2483,
+    2673, 2875, 3214, 3385, 3563, 3732, 4089, 4264, 4510, 4705, 5104, 5241, 5672,
+];
+
+pub(super) const SAJDAH_AYAS: [(usize, usize); 15] = [
+    (7, 206),
+    (13, 15),
+    (16, 50),
+    (17, 109),
+    (19, 58),
+    (22, 18),
+    (22, 77),
+    (25, 60),
+    (27, 26),
+    (32, 15),
+    (38, 24),
+    (41, 38),
+    (53, 62),
+    (84, 21),
+    (96, 19),
+];
+
 pub(super) const PAGE_OFFSETS: [(usize, usize); 604] = [
     (1, 1),
     (2, 1),
@@ -0,0 +1,142 @@
+use std::iter::repeat_n;
+
+use regex::Regex;
+
+use crate::{Quranize, SearchScope, suffix_tree::Index, transliteration::is_harakat};
+
+/// Options for [`Quranize::find_regex`].
+#[derive(Clone, Debug, Default)]
+pub struct RegexQuery {
+    /// Match against aya texts without harakat, e.g. `"بسم"` instead of `"بِسْمِ"`.
+    /// Locations still refer to the original aya texts.
+    pub skeleton: bool,
+    /// Only search in this scope, e.g. a page of the layout in use from
+    /// [`Quranize::page_scope`]. Search in the whole Alquran if `None`.
+    pub scope: Option<SearchScope>,
+    /// Maximum number of locations to return.
+    pub max_results: Option<usize>,
+}
+
+impl Quranize {
+    /// Find a regular expression in Alquran, returning the same locations as [`find`](Self::find),
+    /// in mushaf order. Matches within an aya do not overlap.
+    /// Returns an error if `pattern` is not a valid [`regex`] pattern.
+    ///
+    /// This method requires the `regex` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{Quranize, RegexQuery, SearchScope};
+    ///
+    /// let q = Quranize::new();
+    /// let scope = Some(SearchScope::sura(1));
+    /// let query = RegexQuery { skeleton: true, scope, ..Default::default() };
+    /// let locations = q.find_regex("ال.حيم", &query).unwrap();
+    /// assert_eq!(locations, [(0, 53), (2, 25)]);
+    /// assert!(q.find_regex("(", &query).is_err());
+    /// ```
+    pub fn find_regex(
+        &self,
+        pattern: &str,
+        query: &RegexQuery,
+    ) -> Result<Vec<Index>, regex::Error> {
+        let re = Regex::new(pattern)?;
+        let all = SearchScope::all();
+        let ranges = query.scope.as_ref().unwrap_or(&all).ranges();
+        let locations = ranges.iter().cloned().flatten().flat_map(|i| {
+            let text = self.data[i].3;
+            match query.skeleton {
+                true => {
+                    let (skeleton, offsets) = skeleton_with_offsets(text);
+                    let starts = re.find_iter(&skeleton).map(|m| offsets[m.start()]);
+                    Vec::from_iter(starts.map(|j| (i, j)))
+                }
+                false => Vec::from_iter(re.find_iter(text).map(|m| (i, m.start()))),
+            }
+        });
+        Ok(locations
+            .take(query.max_results.unwrap_or(usize::MAX))
+            .collect())
+    }
+}
+
+fn skeleton_with_offsets(text: &str) -> (String, Vec<usize>) {
+    let mut skeleton = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (j, c) in text.char_indices().filter(|&(_, c)| !is_harakat(c)) {
+        skeleton.push(c);
+        offsets.extend(repeat_n(j, c.len_utf8()));
+    }
+    offsets.push(text.len());
+    (skeleton, offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AYA_COUNT, PageLayout};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_skeleton_with_offsets() {
+        let (skeleton, offsets) = skeleton_with_offsets("بِسْمِ");
+        assert_eq!(skeleton, "بسم");
+        assert_eq!(offsets, [0, 0, 4, 4, 8, 8, 12]);
+        assert_eq!(skeleton_with_offsets(""), (String::new(), vec![0]));
+    }
+
+    #[test]
+    fn test_find_regex() {
+        let q = Quranize::new();
+        let query = RegexQuery::default();
+        assert_eq!(q.find_regex("^عَمَّ يَتَسَاءَلُونَ$", &query).unwrap(), [(5672, 0)]);
+        assert_eq!(q.find_regex("فَبِأَيِّ آلَاءِ", &query).unwrap().len(), 32);
+
+        let skeleton = RegexQuery {
+            skeleton: true,
+            ..Default::default()
+        };
+        let qaala = q.find_regex(r"\bقال\b", &skeleton).unwrap();
+        for &(i, j) in &qaala {
+            assert!(q.data[i].3[j..].starts_with("قَا"));
+        }
+
+        let scoped = |q: &Quranize, scope, max_results| {
+            let query = RegexQuery {
+                scope: Some(scope),
+                max_results,
+                ..skeleton.clone()
+            };
+            q.find_regex(r"\bقال\b", &query).unwrap()
+        };
+        let sura_2 = scoped(&q, SearchScope::sura(2), None);
+        assert!(!sura_2.is_empty() && sura_2.len() < qaala.len());
+        assert!(sura_2.iter().all(|&(i, _)| q.data[i].1 == 2));
+        let juz_30 = scoped(&q, SearchScope::juz(30), None);
+        assert!(juz_30.iter().all(|&(i, _)| q.get_juz(i) == Some(30)));
+        let ranges = scoped(&q, SearchScope::new([7..8, 5672..AYA_COUNT]), None);
+        assert_eq!(ranges, juz_30);
+        assert!(scoped(&q, SearchScope::page(1), None).is_empty());
+        let capped = scoped(&q, SearchScope::all(), Some(3));
+        assert_eq!(capped, qaala[..3]);
+
+        let q = q.with_page_layout(&PageLayout::parse("1:1\n2:1\n2:17").unwrap());
+        let page_2 = |scope| {
+            let query = RegexQuery {
+                scope: Some(scope),
+                ..Default::default()
+            };
+            Vec::from_iter(
+                q.find_regex("^", &query)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(i, _)| i),
+            )
+        };
+        assert_eq!(page_2(q.page_scope(2)), Vec::from_iter(7..23));
+        assert_eq!(page_2(SearchScope::page(2)), Vec::from_iter(7..12));
+
+        assert!(q.find_regex("[", &query).is_err());
+    }
+}
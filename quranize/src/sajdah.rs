@@ -0,0 +1,132 @@
+use crate::{
+    Quranize,
+    quran_metadata::{SAJDAH_AYAS, SURA_STARTS},
+};
+
+/// School of Islamic jurisprudence, whose rulings on sajdah ayas differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Madhhab {
+    /// Hanafi school, with 14 sajdah ayas, all obligatory.
+    Hanafi,
+    /// Maliki school, with 11 sajdah ayas.
+    Maliki,
+    /// Shafi'i school, with 14 sajdah ayas.
+    Shafii,
+    /// Hanbali school, with 14 sajdah ayas.
+    Hanbali,
+}
+
+/// Ruling of the prostration at a sajdah aya, see [`Quranize::get_sajdah`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SajdahKind {
+    /// Prostration is obligatory (wajib).
+    Obligatory,
+    /// Prostration is recommended (sunnah).
+    Recommended,
+}
+
+impl Quranize {
+    /// Check whether aya row `i` (`0..6236`) is one of the 15 ayas marked with `۩`.
+    ///
+    /// # Examples
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// assert!(q.is_sajdah(1159));
+    /// assert!(!q.is_sajdah(0));
+    /// ```
+    pub fn is_sajdah(&self, i: usize) -> bool {
+        sajdah_rows().any(|(j, _)| j == i)
+    }
+
+    /// Get the ruling of the prostration at aya row `i` (`0..6236`) according to `madhhab`.
+    /// Returns `None` if the aya is not a sajdah aya in that madhhab, e.g. 22:77 in the Hanafi
+    /// madhhab, or 38:24 in the Shafi'i and Hanbali madhhabs, where it is a prostration of
+    /// gratitude instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use quranize::{Madhhab, SajdahKind};
+    ///
+    /// let q = quranize::Quranize::new();
+    /// assert_eq!(q.get_sajdah(1159, Madhhab::Hanafi), Some(SajdahKind::Obligatory));
+    /// assert_eq!(q.get_sajdah(1159, Madhhab::Shafii), Some(SajdahKind::Recommended));
+    /// assert_eq!(q.get_sajdah(6124, Madhhab::Maliki), None);
+    /// assert_eq!(q.get_sajdah(0, Madhhab::Hanafi), None);
+    /// ```
+    pub fn get_sajdah(&self, i: usize, madhhab: Madhhab) -> Option<SajdahKind> {
+        { sajdah_rows() }
+            .find(|&(j, _)| j == i)
+            .and_then(|(_, sura_aya)| sajdah_kind(sura_aya, madhhab))
+    }
+
+    /// List the sajdah ayas according to `madhhab`, in mushaf order, as a list of tuple:
+    /// - `usize`: aya row
+    /// - `SajdahKind`: ruling of the prostration
+    ///
+    /// # Examples
+    /// ```
+    /// use quranize::Madhhab;
+    ///
+    /// let q = quranize::Quranize::new();
+    /// assert_eq!(q.get_sajdahs(Madhhab::Hanafi).len(), 14);
+    /// assert_eq!(q.get_sajdahs(Madhhab::Maliki).len(), 11);
+    /// let (i, _) = q.get_sajdahs(Madhhab::Shafii)[0];
+    /// assert_eq!(q.get_data(i).map(|&(_, s, a, _)| (s, a)), Some((7, 206)));
+    /// ```
+    pub fn get_sajdahs(&self, madhhab: Madhhab) -> Vec<(usize, SajdahKind)> {
+        { sajdah_rows() }
+            .filter_map(|(i, sura_aya)| sajdah_kind(sura_aya, madhhab).map(|k| (i, k)))
+            .collect()
+    }
+}
+
+fn sajdah_rows() -> impl Iterator<Item = (usize, (usize, usize))> {
+    { SAJDAH_AYAS.into_iter() }.map(|(s, a)| (SURA_STARTS[s - 1] + a - 1, (s, a)))
+}
+
+fn sajdah_kind(sura_aya: (usize, usize), madhhab: Madhhab) -> Option<SajdahKind> {
+    match (madhhab, sura_aya) {
+        (Madhhab::Hanafi, (22, 77)) => None,
+        (Madhhab::Hanafi, _) => Some(SajdahKind::Obligatory),
+        (Madhhab::Maliki, (22, 77) | (53, 62) | (84, 21) | (96, 19)) => None,
+        (Madhhab::Shafii | Madhhab::Hanbali, (38, 24)) => None,
+        _ => Some(SajdahKind::Recommended),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AYA_COUNT, QURAN_TXT};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_sajdah_rows_match_text() {
+        let marked = QURAN_TXT.lines().take(AYA_COUNT).enumerate();
+        let marked = Vec::from_iter(marked.filter(|(_, l)| l.contains('۩')).map(|(i, _)| i));
+        assert_eq!(Vec::from_iter(sajdah_rows().map(|(i, _)| i)), marked);
+    }
+
+    #[test]
+    fn test_get_sajdahs() {
+        let q = Quranize::new();
+        for madhhab in [
+            Madhhab::Hanafi,
+            Madhhab::Maliki,
+            Madhhab::Shafii,
+            Madhhab::Hanbali,
+        ] {
+            for (i, kind) in q.get_sajdahs(madhhab) {
+                assert!(q.is_sajdah(i));
+                assert_eq!(q.get_sajdah(i, madhhab), Some(kind));
+            }
+        }
+        assert_eq!(q.get_sajdahs(Madhhab::Shafii).len(), 14);
+        assert_eq!(
+            q.get_sajdah(3993, Madhhab::Hanafi),
+            Some(SajdahKind::Obligatory)
+        );
+        assert_eq!(q.get_sajdah(3993, Madhhab::Hanbali), None);
+        assert!(!q.is_sajdah(AYA_COUNT));
+    }
+}
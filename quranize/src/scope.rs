@@ -0,0 +1,134 @@
+use std::ops::Range;
+
+use crate::{
+    PageLayout,
+    quran_metadata::{AYA_COUNT, JUZ_STARTS, SURA_STARTS},
+};
+
+/// Set of aya rows (`0..6236`) a search is restricted to, for
+/// [`Quranize::find_in`](crate::Quranize::find_in) and
+/// [`Quranize::encode_in`](crate::Quranize::encode_in).
+///
+/// # Examples
+///
+/// ```
+/// use quranize::SearchScope;
+///
+/// let scope = SearchScope::sura(1).union(&SearchScope::juz(30));
+/// assert!(scope.contains(0) && scope.contains(5672));
+/// assert!(!scope.contains(7));
+/// assert_eq!(SearchScope::page(1), SearchScope::new([0..7]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchScope {
+    ranges: Vec<Range<usize>>,
+}
+
+impl SearchScope {
+    /// Create a scope covering the given ranges of aya rows. Ranges may overlap or be unordered.
+    pub fn new(ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        let mut ranges = Vec::from_iter(
+            { ranges.into_iter() }
+                .map(|r| r.start.min(AYA_COUNT)..r.end.min(AYA_COUNT))
+                .filter(|r| !r.is_empty()),
+        );
+        ranges.sort_unstable_by_key(|r| r.start);
+        let ranges = ranges
+            .into_iter()
+            .fold(Vec::<Range<usize>>::new(), |mut merged, r| {
+                match merged.last_mut() {
+                    Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                    _ => merged.push(r),
+                }
+                merged
+            });
+        Self { ranges }
+    }
+
+    /// Create a scope covering the whole Alquran.
+    pub fn all() -> Self {
+        Self::new(Some(0..AYA_COUNT))
+    }
+
+    /// Create a scope covering a sura (`1..=114`). Other numbers give an empty scope.
+    pub fn sura(sura: u8) -> Self {
+        Self::new(Self::nth_range(&SURA_STARTS, sura as usize))
+    }
+
+    /// Create a scope covering a juz (`1..=30`). Other numbers give an empty scope.
+    pub fn juz(juz: u8) -> Self {
+        Self::new(Self::nth_range(&JUZ_STARTS, juz as usize))
+    }
+
+    /// Create a scope covering a page (`1..=604`) of the Madinah mushaf. Other numbers give an
+    /// empty scope. See [`Quranize::page_scope`](crate::Quranize::page_scope) for the page
+    /// layout in use.
+    pub fn page(page: u16) -> Self {
+        PageLayout::madinah().scope(page)
+    }
+
+    pub(crate) fn nth_range(starts: &[usize], n: usize) -> Option<Range<usize>> {
+        let start = *starts.get(n.checked_sub(1)?)?;
+        Some(start..starts.get(n).copied().unwrap_or(AYA_COUNT))
+    }
+
+    /// Create a scope covering every aya row of `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(self.ranges.iter().chain(&other.ranges).cloned())
+    }
+
+    /// Check whether aya row `i` is in the scope.
+    pub fn contains(&self, i: usize) -> bool {
+        let k = self.ranges.partition_point(|r| r.end <= i);
+        self.ranges.get(k).is_some_and(|r| r.contains(&i))
+    }
+
+    /// Get the ranges of aya rows in the scope, sorted and non-overlapping.
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    pub(crate) fn intersects(&self, span: &Range<usize>) -> bool {
+        let k = self.ranges.partition_point(|r| r.end <= span.start);
+        self.ranges.get(k).is_some_and(|r| r.start < span.end)
+    }
+
+    pub(crate) fn covers(&self, span: &Range<usize>) -> bool {
+        let k = self.ranges.partition_point(|r| r.end <= span.start);
+        { self.ranges.get(k) }.is_some_and(|r| r.start <= span.start && span.end <= r.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_search_scope() {
+        let spans = |s: &SearchScope| Vec::from_iter(s.ranges().iter().map(|r| (r.start, r.end)));
+        let scope = SearchScope::new([5..10, 0..2, 8..12, 12..13, 6300..7000]);
+        assert_eq!(spans(&scope), [(0, 2), (5, 13)]);
+        assert!(scope.contains(1) && scope.contains(12));
+        assert!(!scope.contains(2) && !scope.contains(13));
+        assert!(scope.intersects(&(1..6)) && scope.intersects(&(12..20)));
+        assert!(!scope.intersects(&(2..5)) && !scope.intersects(&(13..20)));
+        assert!(scope.covers(&(6..13)) && !scope.covers(&(1..6)));
+
+        assert_eq!(spans(&SearchScope::all()), [(0, AYA_COUNT)]);
+        assert_eq!(spans(&SearchScope::sura(2)), [(7, 293)]);
+        assert_eq!(spans(&SearchScope::sura(114)), [(6230, AYA_COUNT)]);
+        assert_eq!(spans(&SearchScope::juz(30)), [(5672, AYA_COUNT)]);
+        assert_eq!(spans(&SearchScope::page(2)), [(7, 12)]);
+        assert_eq!(spans(&SearchScope::page(604)), [(6221, AYA_COUNT)]);
+        for empty in [
+            SearchScope::sura(0),
+            SearchScope::juz(31),
+            SearchScope::page(605),
+        ] {
+            assert!(empty.ranges().is_empty());
+        }
+        let scope = SearchScope::sura(1).union(&SearchScope::sura(2));
+        assert_eq!(spans(&scope), [(0, 293)]);
+    }
+}
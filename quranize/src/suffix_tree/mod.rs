@@ -1,8 +1,14 @@
-use std::collections::{btree_set::Range, BTreeSet};
+use std::cmp::Reverse;
+use std::collections::{btree_set::Range, BTreeSet, BinaryHeap};
+use std::ops;
+
+use crate::SearchScope;
 
 mod suffix_iter;
 
-type Vertex = (Option<Index>, usize, bool);
+pub(super) use suffix_iter::{is_separator, word_count};
+
+type Vertex = (Option<Index>, usize, bool, ops::Range<usize>);
 pub(super) type Index = (usize, usize);
 pub(super) type Edge<'a> = (usize, usize, &'a str);
 
@@ -14,28 +20,36 @@ pub(super) struct SuffixTree<'a> {
 impl<'a> SuffixTree<'a> {
     pub(super) fn with_capacity(capacity: usize) -> Self {
         let mut vertices = Vec::with_capacity(capacity);
-        vertices.push((None, 0, false));
+        vertices.push((None, 0, false, 0..0));
         let edges = Default::default();
         Self { vertices, edges }
     }
 
-    pub(super) fn construct(&mut self, i: usize, s: &'a str) {
-        suffix_iter::suffix_iter(s).for_each(|(j, s)| self.construct_suffix((i, j), 0, s));
+    /// Add the suffixes of aya text `s` in aya row `i`, where the aya starts at byte offset
+    /// `start` of `s`, i.e. after a prefixed basmalah.
+    pub(super) fn construct(&mut self, i: usize, s: &'a str, start: usize) {
+        suffix_iter::suffix_iter(s).for_each(|(j, s)| {
+            let muq = j
+                .checked_sub(start)
+                .is_some_and(|j| contains_harf_muqottoah((i, j)));
+            self.construct_suffix((i, j), 0, s, muq)
+        });
     }
 
-    fn construct_suffix(&mut self, i: Index, v: usize, s: &'a str) {
+    fn construct_suffix(&mut self, i: Index, v: usize, s: &'a str, muq: bool) {
         let edge_prefix_pair = self
             .edges_from(v)
             .find_map(|&(v, w, l)| Some((v, w, l)).zip(Self::longest_prefix(s, l)));
         match edge_prefix_pair {
             Some(((_, w, l), p)) if l.len() == p.len() && s.len() > p.len() => {
-                self.construct_suffix(i, w, &s[p.len()..]);
+                self.construct_suffix(i, w, &s[p.len()..], muq);
                 self.vertices[v].2 |= self.vertices[w].2;
             }
             Some(((v, w, l), p)) => {
                 self.edges.remove(&(v, w, l));
-                let x = self.add_vertex((None, self.vertices[w].1 + 1, false));
-                let y = self.add_vertex((Some(i), 1, contains_harf_muqottoah(i)));
+                let span = self.vertices[w].3.start..i.0 + 1;
+                let x = self.add_vertex((None, self.vertices[w].1 + 1, false, span));
+                let y = self.add_vertex((Some(i), 1, muq, i.0..i.0 + 1));
                 self.edges.insert((v, x, p));
                 self.edges.insert((x, w, &l[p.len()..]));
                 self.edges.insert((x, y, &s[p.len()..]));
@@ -43,12 +57,13 @@ impl<'a> SuffixTree<'a> {
                 self.vertices[v].2 |= self.vertices[x].2;
             }
             None => {
-                let w = self.add_vertex((Some(i), 1, contains_harf_muqottoah(i)));
+                let w = self.add_vertex((Some(i), 1, muq, i.0..i.0 + 1));
                 self.edges.insert((v, w, s));
                 self.vertices[v].2 |= self.vertices[w].2;
             }
         }
         self.vertices[v].1 += 1;
+        self.vertices[v].3.end = i.0 + 1;
     }
 
     pub(super) fn edges_from(&self, v: usize) -> Range<'_, Edge<'a>> {
@@ -70,6 +85,22 @@ impl<'a> SuffixTree<'a> {
         self.vertices[v].1
     }
 
+    pub(super) fn count_data_in(&self, v: usize, scope: &SearchScope) -> usize {
+        let (data, count, _, span) = &self.vertices[v];
+        match (scope.covers(span), scope.intersects(span)) {
+            (true, _) => *count,
+            (false, false) => 0,
+            (false, true) => {
+                let own_count = data.filter(|&(i, _)| scope.contains(i)).is_some() as usize;
+                let edges = self.edges_from(v);
+                own_count
+                    + edges
+                        .map(|&(_, w, _)| self.count_data_in(w, scope))
+                        .sum::<usize>()
+            }
+        }
+    }
+
     pub(super) fn find(&self, s: &str, v: usize) -> Vec<Index> {
         self.edges_from(v)
             .flat_map(|&(_, w, l)| match (s.strip_prefix(l), l.strip_prefix(s)) {
@@ -80,6 +111,57 @@ impl<'a> SuffixTree<'a> {
             .collect()
     }
 
+    pub(super) fn find_in(&self, s: &str, v: usize, scope: &SearchScope) -> Vec<Index> {
+        self.edges_from(v)
+            .filter(|&&(_, w, _)| scope.intersects(&self.vertices[w].3))
+            .flat_map(|&(_, w, l)| match (s.strip_prefix(l), l.strip_prefix(s)) {
+                (_, Some(_)) if !s.is_empty() => self.collect_data_in(w, scope),
+                (Some(s), _) => self.find_in(s, w, scope),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    pub(super) fn find_iter(&self, s: &str, v: usize) -> DataIter<'_, 'a> {
+        let stack = self.matched_vertices(s, v);
+        DataIter { tree: self, stack }
+    }
+
+    /// Like [`find_iter`](Self::find_iter), but yielding in sorted order, and only the data
+    /// coming after `after`. Subtrees with no aya row after it are pruned.
+    pub(super) fn find_sorted_iter(
+        &self,
+        s: &str,
+        v: usize,
+        after: Option<Index>,
+    ) -> SortedDataIter<'_, 'a> {
+        let mut iter = SortedDataIter {
+            tree: self,
+            heap: BinaryHeap::new(),
+            after,
+        };
+        self.matched_vertices(s, v)
+            .into_iter()
+            .for_each(|w| iter.push_vertex(w));
+        iter
+    }
+
+    /// Vertices whose subtrees hold exactly the data where `s` is found, in edge order.
+    fn matched_vertices(&self, s: &str, v: usize) -> Vec<usize> {
+        let mut stack = vec![];
+        let mut current = Some((s, v));
+        while let Some((s, v)) = current.take() {
+            for &(_, w, l) in self.edges_from(v) {
+                match (s.strip_prefix(l), l.strip_prefix(s)) {
+                    (_, Some(_)) if !s.is_empty() => stack.push(w),
+                    (Some(s), _) => current = Some((s, w)),
+                    _ => {}
+                }
+            }
+        }
+        stack
+    }
+
     pub(super) fn collect_data(&self, v: usize) -> Vec<Index> {
         let mut buffer = Vec::with_capacity(self.count_data(v));
         self.collect_data_to_buffer(v, &mut buffer);
@@ -91,6 +173,81 @@ impl<'a> SuffixTree<'a> {
         self.edges_from(v)
             .for_each(|&(_, w, _)| self.collect_data_to_buffer(w, buffer));
     }
+
+    fn collect_data_in(&self, v: usize, scope: &SearchScope) -> Vec<Index> {
+        let mut buffer = Vec::with_capacity(self.count_data_in(v, scope));
+        self.collect_data_in_to_buffer(v, scope, &mut buffer);
+        buffer
+    }
+
+    fn collect_data_in_to_buffer(&self, v: usize, scope: &SearchScope, buffer: &mut Vec<Index>) {
+        let (data, _, _, span) = &self.vertices[v];
+        if scope.intersects(span) {
+            { data.filter(|&(i, _)| scope.contains(i)).into_iter() }.for_each(|x| buffer.push(x));
+            self.edges_from(v)
+                .for_each(|&(_, w, _)| self.collect_data_in_to_buffer(w, scope, buffer));
+        }
+    }
+}
+
+/// Lazy pre-order traversal over the data of a subtree, yielding in the same order as
+/// [`SuffixTree::collect_data`].
+pub(super) struct DataIter<'t, 'a> {
+    tree: &'t SuffixTree<'a>,
+    stack: Vec<usize>,
+}
+
+impl Iterator for DataIter<'_, '_> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        while let Some(v) = self.stack.pop() {
+            let children = self.tree.edges_from(v).rev().map(|&(_, w, _)| w);
+            self.stack.extend(children);
+            if let Some(i) = self.tree.vertices[v].0 {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+/// Lazy best-first traversal over the data of several subtrees in sorted order. Vertices are
+/// keyed by their first aya row, a lower bound of every index in their subtree, so an index is
+/// only yielded once no vertex left in the heap can hold a smaller one.
+pub(super) struct SortedDataIter<'t, 'a> {
+    tree: &'t SuffixTree<'a>,
+    heap: BinaryHeap<Reverse<(Index, Option<usize>)>>,
+    after: Option<Index>,
+}
+
+impl SortedDataIter<'_, '_> {
+    fn push_vertex(&mut self, v: usize) {
+        let (data, _, _, span) = &self.tree.vertices[v];
+        if self.after.is_none_or(|(i, _)| span.end > i) {
+            self.heap.push(Reverse(((span.start, 0), Some(v))));
+            { data.filter(|&x| self.after.is_none_or(|after| x > after)) }
+                .into_iter()
+                .for_each(|x| self.heap.push(Reverse((x, None))));
+        }
+    }
+}
+
+impl Iterator for SortedDataIter<'_, '_> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        while let Some(Reverse((x, v))) = self.heap.pop() {
+            match v {
+                None => return Some(x),
+                Some(v) => {
+                    let children = Vec::from_iter(self.tree.edges_from(v).map(|&(_, w, _)| w));
+                    children.into_iter().for_each(|w| self.push_vertex(w));
+                }
+            }
+        }
+        None
+    }
 }
 
 fn contains_harf_muqottoah(i: Index) -> bool {
@@ -30,8 +30,16 @@ impl<'a> Iterator for SuffixIter<'a> {
     }
 }
 
-fn non_separator((_, c): &(usize, char)) -> bool {
-    !matches!(c, ' ' | '\u{06D6}'..='\u{06DC}' | '\n')
+fn non_separator(&(_, c): &(usize, char)) -> bool {
+    !is_separator(c)
+}
+
+pub(crate) fn is_separator(c: char) -> bool {
+    matches!(c, ' ' | '\u{06D6}'..='\u{06DC}' | '\n')
+}
+
+pub(crate) fn word_count(s: &str) -> usize {
+    s.split(is_separator).filter(|w| !w.is_empty()).count()
 }
 
 #[cfg(test)]
@@ -39,6 +47,13 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_word_count() {
+        assert_eq!(word_count(""), 0);
+        assert_eq!(word_count(" "), 0);
+        assert_eq!(word_count(" ab cd ۖ ef\n"), 3);
+    }
+
     #[test]
     fn test_suffix_iter() {
         let mut it = suffix_iter("ab cde  fg   h\n\n");
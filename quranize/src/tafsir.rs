@@ -0,0 +1,184 @@
+use std::{collections::BTreeMap, fs, io, ops::Range, path::Path};
+
+use crate::words::aya_row;
+
+/// Store of tafsir (commentary) texts from named sources, each text applying to a range of aya
+/// rows (`0..6236`).
+///
+/// # Examples
+///
+/// ```
+/// use quranize::TafsirStore;
+///
+/// let mut store = TafsirStore::default();
+/// store.load("short", "1|1|Opening with the name of Allah.\n2|1-5|Traits of the pious.").unwrap();
+/// store.load("notes", "2|3|Believing in the unseen.").unwrap();
+/// assert_eq!(store.get(0), [("short", 0..1, "Opening with the name of Allah.")]);
+/// assert_eq!(store.get(9).len(), 2);
+/// assert_eq!(store.search("the pious"), [("short", 7..12, 10)]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TafsirStore {
+    sources: BTreeMap<String, Vec<(Range<usize>, String)>>,
+}
+
+impl TafsirStore {
+    /// Load tafsir texts of source `name` from lines of `sura|aya|text`, where `aya` is an aya
+    /// number or a range of aya numbers like `1-5`, both inclusive. Empty lines and comment lines
+    /// starting with `#` are skipped. The texts are added to the ones already loaded for `name`.
+    /// Returns the line number (starting from 1) of the first malformed line as an error, in
+    /// which case nothing is added.
+    pub fn load(&mut self, name: &str, text: &str) -> Result<(), usize> {
+        let mut entries = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            entries.push(parse_entry(line).ok_or(n + 1)?);
+        }
+        let source = self.sources.entry(name.to_string()).or_default();
+        source.extend(entries);
+        source.sort_by_key(|(rows, _)| (rows.start, rows.end));
+        Ok(())
+    }
+
+    /// Load tafsir texts of source `name` from a file on local disk, like [`load`](Self::load).
+    /// A malformed line gives an error of kind [`io::ErrorKind::InvalidData`].
+    pub fn load_file(&mut self, name: &str, path: impl AsRef<Path>) -> io::Result<()> {
+        let text = fs::read_to_string(path)?;
+        self.load(name, &text)
+            .map_err(|n| io::Error::new(io::ErrorKind::InvalidData, format!("malformed line {n}")))
+    }
+
+    /// Remove source `name`, returning whether it was loaded.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.sources.remove(name).is_some()
+    }
+
+    /// Get the names of all loaded sources, sorted.
+    pub fn sources(&self) -> Vec<&str> {
+        Vec::from_iter(self.sources.keys().map(String::as_str))
+    }
+
+    /// Get the tafsir texts applying to aya row `i` (`0..6236`), returning a list of tuple,
+    /// sorted by source name:
+    /// - `&str`: source name
+    /// - `Range<usize>`: aya rows the text applies to
+    /// - `&str`: tafsir text
+    pub fn get(&self, i: usize) -> Vec<(&str, Range<usize>, &str)> {
+        { self.sources.iter() }
+            .flat_map(|(name, entries)| {
+                { entries.iter() }
+                    .take_while(move |(rows, _)| rows.start <= i)
+                    .filter(move |(rows, _)| rows.contains(&i))
+                    .map(move |(rows, text)| (name.as_str(), rows.clone(), text.as_str()))
+            })
+            .collect()
+    }
+
+    /// Get the tafsir texts of source `name` applying to aya row `i`, like [`get`](Self::get).
+    pub fn get_from(&self, name: &str, i: usize) -> Vec<(Range<usize>, &str)> {
+        { self.get(i).into_iter() }
+            .filter(|&(n, _, _)| n == name)
+            .map(|(_, rows, text)| (rows, text))
+            .collect()
+    }
+
+    /// Search `query` in all tafsir texts, ignoring ASCII case, returning a list of tuple, sorted
+    /// by source name and then by aya rows:
+    /// - `&str`: source name
+    /// - `Range<usize>`: aya rows the text applies to
+    /// - `usize`: byte offset of the match in the tafsir text
+    pub fn search(&self, query: &str) -> Vec<(&str, Range<usize>, usize)> {
+        if query.is_empty() {
+            return vec![];
+        }
+        let query = query.to_ascii_lowercase();
+        let mut hits = Vec::new();
+        for (name, entries) in &self.sources {
+            for (rows, text) in entries {
+                let text = text.to_ascii_lowercase();
+                let offsets = text.match_indices(&query).map(|(j, _)| j);
+                hits.extend(offsets.map(|j| (name.as_str(), rows.clone(), j)));
+            }
+        }
+        hits
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(Range<usize>, String)> {
+    let mut fields = line.splitn(3, '|');
+    let sura = fields.next()?.trim().parse().ok()?;
+    let ayas = fields.next()?.trim();
+    let text = fields.next()?.trim();
+    let (first, last) = ayas.split_once('-').unwrap_or((ayas, ayas));
+    let first = aya_row(sura, first.trim().parse().ok()?)?;
+    let last = aya_row(sura, last.trim().parse().ok()?)?;
+    (first <= last).then(|| (first..last + 1, text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(
+            parse_entry("1|2|Praise"),
+            Some((1..2, "Praise".to_string()))
+        );
+        assert_eq!(
+            parse_entry("2 | 1-5 | a|b"),
+            Some((7..12, "a|b".to_string()))
+        );
+        assert_eq!(parse_entry("1|7-8|x"), None);
+        assert_eq!(parse_entry("1|3-2|x"), None);
+        assert_eq!(parse_entry("1|2"), None);
+        assert_eq!(parse_entry("x|2|y"), None);
+    }
+
+    #[test]
+    fn test_load() {
+        let mut store = TafsirStore::default();
+        store
+            .load("a", "# sura 1\n1|2-3|Second\n\n1|1|First")
+            .unwrap();
+        assert_eq!(store.get(0), [("a", 0..1, "First")]);
+        assert_eq!(store.get_from("a", 2), [(1..3, "Second")]);
+        assert_eq!(store.load("a", "1|4|Fourth\n1|8|x"), Err(2));
+        assert!(store.get(3).is_empty());
+        store.load("a", "1|1-7|Whole sura").unwrap();
+        assert_eq!(store.get(0).len(), 2);
+        assert!(store.get_from("b", 0).is_empty());
+
+        store.load("b", "114|6|Last").unwrap();
+        assert_eq!(store.sources(), ["a", "b"]);
+        assert_eq!(store.get(6235), [("b", 6235..6236, "Last")]);
+        assert!(store.remove("b") && !store.remove("b"));
+        assert!(store.get(6235).is_empty());
+
+        let name = format!("quranize-test-tafsir-{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, "1|5|Fifth\n").unwrap();
+        store.load_file("c", &path).unwrap();
+        assert_eq!(store.get_from("c", 4), [(4..5, "Fifth")]);
+        fs::write(&path, "1|5\n").unwrap();
+        let error = store.load_file("c", &path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_search() {
+        let mut store = TafsirStore::default();
+        store
+            .load("a", "1|1|Rahman and Rahim\n1|3|rahman again")
+            .unwrap();
+        store.load("b", "1|3|الرحمن الرحيم").unwrap();
+        assert_eq!(store.search("RAHMAN"), [("a", 0..1, 0), ("a", 2..3, 0)]);
+        assert_eq!(store.search("الرحيم"), [("b", 2..3, "الرحمن ".len())]);
+        assert!(store.search("").is_empty());
+        assert!(store.search("missing").is_empty());
+    }
+}
@@ -1,5 +1,8 @@
 pub(super) const LINE_FEED: char = '\u{000A}';
 pub(super) const SPACE: char = '\u{0020}';
+pub(super) const ARABIC_SMALL_FATHA: char = '\u{0618}';
+pub(super) const ARABIC_SMALL_DAMMA: char = '\u{0619}';
+pub(super) const ARABIC_SMALL_KASRA: char = '\u{061A}';
 pub(super) const ARABIC_LETTER_HAMZA: char = '\u{0621}';
 pub(super) const ARABIC_LETTER_ALEF_WITH_MADDA_ABOVE: char = '\u{0622}';
 pub(super) const ARABIC_LETTER_ALEF_WITH_HAMZA_ABOVE: char = '\u{0623}';
@@ -26,6 +29,7 @@ pub(super) const ARABIC_LETTER_TAH: char = '\u{0637}';
 pub(super) const ARABIC_LETTER_ZAH: char = '\u{0638}';
 pub(super) const ARABIC_LETTER_AIN: char = '\u{0639}';
 pub(super) const ARABIC_LETTER_GHAIN: char = '\u{063A}';
+pub(super) const ARABIC_TATWEEL: char = '\u{0640}';
 pub(super) const ARABIC_LETTER_FEH: char = '\u{0641}';
 pub(super) const ARABIC_LETTER_QAF: char = '\u{0642}';
 pub(super) const ARABIC_LETTER_KAF: char = '\u{0643}';
@@ -44,7 +48,13 @@ pub(super) const ARABIC_DAMMA: char = '\u{064F}';
 pub(super) const ARABIC_KASRA: char = '\u{0650}';
 pub(super) const ARABIC_SHADDA: char = '\u{0651}';
 pub(super) const ARABIC_SUKUN: char = '\u{0652}';
+pub(super) const ARABIC_MADDAH_ABOVE: char = '\u{0653}';
+pub(super) const ARABIC_HAMZA_ABOVE: char = '\u{0654}';
+pub(super) const ARABIC_HAMZA_BELOW: char = '\u{0655}';
+pub(super) const ARABIC_SUBSCRIPT_ALEF: char = '\u{0656}';
 pub(super) const ARABIC_LETTER_SUPERSCRIPT_ALEF: char = '\u{0670}';
+pub(super) const ARABIC_LETTER_ALEF_WASLA: char = '\u{0671}';
+pub(super) const ARABIC_LETTER_FARSI_YEH: char = '\u{06CC}';
 pub(super) const ARABIC_SMALL_HIGH_LIGATURE_SAD_WITH_LAM_WITH_ALEF_MAKSURA: char = '\u{06D6}';
 pub(super) const ARABIC_SMALL_HIGH_LIGATURE_QAF_WITH_LAM_WITH_ALEF_MAKSURA: char = '\u{06D7}';
 pub(super) const ARABIC_SMALL_HIGH_MEEM_INITIAL_FORM: char = '\u{06D8}';
@@ -52,4 +62,23 @@ pub(super) const ARABIC_SMALL_HIGH_LAM_ALEF: char = '\u{06D9}';
 pub(super) const ARABIC_SMALL_HIGH_JEEM: char = '\u{06DA}';
 pub(super) const ARABIC_SMALL_HIGH_THREE_DOTS: char = '\u{06DB}';
 pub(super) const ARABIC_SMALL_HIGH_SEEN: char = '\u{06DC}';
+pub(super) const ARABIC_END_OF_AYAH: char = '\u{06DD}';
+pub(super) const ARABIC_START_OF_RUB_EL_HIZB: char = '\u{06DE}';
+pub(super) const ARABIC_SMALL_HIGH_ROUNDED_ZERO: char = '\u{06DF}';
+pub(super) const ARABIC_SMALL_HIGH_UPRIGHT_RECTANGULAR_ZERO: char = '\u{06E0}';
+pub(super) const ARABIC_SMALL_HIGH_DOTLESS_HEAD_OF_KHAH: char = '\u{06E1}';
+pub(super) const ARABIC_SMALL_HIGH_MEEM_ISOLATED_FORM: char = '\u{06E2}';
+pub(super) const ARABIC_SMALL_LOW_SEEN: char = '\u{06E3}';
+pub(super) const ARABIC_SMALL_HIGH_MADDA: char = '\u{06E4}';
+pub(super) const ARABIC_SMALL_WAW: char = '\u{06E5}';
+pub(super) const ARABIC_SMALL_YEH: char = '\u{06E6}';
+pub(super) const ARABIC_SMALL_HIGH_YEH: char = '\u{06E7}';
+pub(super) const ARABIC_SMALL_HIGH_NOON: char = '\u{06E8}';
 pub(super) const ARABIC_PLACE_OF_SAJDAH: char = '\u{06E9}';
+pub(super) const ARABIC_EMPTY_CENTRE_LOW_STOP: char = '\u{06EA}';
+pub(super) const ARABIC_EMPTY_CENTRE_HIGH_STOP: char = '\u{06EB}';
+pub(super) const ARABIC_ROUNDED_HIGH_STOP_WITH_FILLED_CENTRE: char = '\u{06EC}';
+pub(super) const ARABIC_SMALL_LOW_MEEM: char = '\u{06ED}';
+pub(super) const ARABIC_OPEN_FATHATAN: char = '\u{08F0}';
+pub(super) const ARABIC_OPEN_DAMMATAN: char = '\u{08F1}';
+pub(super) const ARABIC_OPEN_KASRATAN: char = '\u{08F2}';
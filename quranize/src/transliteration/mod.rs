@@ -1,6 +1,54 @@
 mod chars;
+pub(super) mod statistics;
+pub(super) mod tajweed;
+pub(super) mod waqf;
 
 use chars::*;
+use waqf::{pause_map, pause_tail};
+
+/// How quran text is read into transliteration while walking the suffix tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Reading {
+    /// Plain reading, as in [`Quranize::encode`](crate::Quranize::encode).
+    Plain,
+    /// Reading harf muqottoah by their letter names, e.g. `"alif lam mim"` for `"الم"`.
+    Muqottoah,
+    /// Reading with pause forms, as in
+    /// [`Quranize::encode_at_pause`](crate::Quranize::encode_at_pause): the harakat last read in
+    /// pause form while the stop after it has not been reached yet, and whether any pause form
+    /// was read.
+    Pause(Option<char>, bool),
+}
+
+impl Reading {
+    /// Readings of char `c` after char `context`, each with whether it is contextual and the
+    /// reading of the next char.
+    pub(super) fn tsls(
+        self,
+        context: Option<char>,
+        c: char,
+    ) -> impl Iterator<Item = (&'static str, bool, Self)> {
+        let (tsls, contextual_tsls, pause_tsls, tail) = match self {
+            Self::Plain => (map(c), contextual_map(context, c), &[][..], None),
+            Self::Muqottoah => (harf_muqottoah_map(c), &[][..], &[][..], None),
+            Self::Pause(None, _) => (
+                map(c),
+                contextual_map(context, c),
+                pause_map(context, c),
+                None,
+            ),
+            Self::Pause(Some(p), _) => (&[][..], &[][..], &[][..], pause_tail(p, c)),
+        };
+        { tsls.iter().map(move |&tsl| (tsl, false, self)) }
+            .chain(contextual_tsls.iter().map(move |&tsl| (tsl, true, self)))
+            .chain(
+                pause_tsls
+                    .iter()
+                    .map(move |&tsl| (tsl, false, Self::Pause(Some(c), true))),
+            )
+            .chain(tail.map(|next| ("", false, Self::Pause(next, true))))
+    }
+}
 
 pub(super) fn map(c: char) -> &'static [&'static str] {
     match c {
@@ -43,18 +91,29 @@ pub(super) fn map(c: char) -> &'static [&'static str] {
         ARABIC_LETTER_HEH => &["h"],
         ARABIC_LETTER_WAW => &["w", "u", "uu"],
         ARABIC_LETTER_ALEF_MAKSURA => &["a", "aa", "y", "i", "ii"],
-        ARABIC_LETTER_YEH => &["y", "i", "ii"],
-
-        ARABIC_FATHATAN => &["an", "on", ""],
-        ARABIC_DAMMATAN => &["un"],
-        ARABIC_KASRATAN => &["in"],
-        ARABIC_FATHA => &["a", "o"],
-        ARABIC_DAMMA => &["u"],
-        ARABIC_KASRA => &["i"],
+        ARABIC_LETTER_YEH | ARABIC_LETTER_FARSI_YEH => &["y", "i", "ii"],
+
+        ARABIC_FATHATAN | ARABIC_OPEN_FATHATAN => &["an", "on", ""],
+        ARABIC_DAMMATAN | ARABIC_OPEN_DAMMATAN => &["un"],
+        ARABIC_KASRATAN | ARABIC_OPEN_KASRATAN => &["in"],
+        ARABIC_FATHA | ARABIC_SMALL_FATHA => &["a", "o"],
+        ARABIC_DAMMA | ARABIC_SMALL_DAMMA => &["u"],
+        ARABIC_KASRA | ARABIC_SMALL_KASRA => &["i"],
         ARABIC_SHADDA => &[""],
-        ARABIC_SUKUN => &[""],
+        ARABIC_SUKUN | ARABIC_SMALL_HIGH_DOTLESS_HEAD_OF_KHAH => &[""],
+        ARABIC_MADDAH_ABOVE | ARABIC_SMALL_HIGH_MADDA => &[""],
+        ARABIC_HAMZA_ABOVE | ARABIC_HAMZA_BELOW => &["", "'", "k"],
+        ARABIC_SUBSCRIPT_ALEF => &["i", "ii"],
 
         ARABIC_LETTER_SUPERSCRIPT_ALEF => &["a", "aa", "o", "oo"],
+        ARABIC_LETTER_ALEF_WASLA => &["", "a", "i", "u"],
+
+        ARABIC_SMALL_HIGH_ROUNDED_ZERO | ARABIC_SMALL_HIGH_UPRIGHT_RECTANGULAR_ZERO => &[""],
+        ARABIC_SMALL_HIGH_MEEM_ISOLATED_FORM | ARABIC_SMALL_LOW_MEEM => &["", "m"],
+        ARABIC_SMALL_LOW_SEEN => &[""],
+        ARABIC_SMALL_WAW => &["", "u", "uu"],
+        ARABIC_SMALL_YEH | ARABIC_SMALL_HIGH_YEH => &["", "i", "ii"],
+        ARABIC_SMALL_HIGH_NOON => &["n"],
 
         ARABIC_SMALL_HIGH_LIGATURE_SAD_WITH_LAM_WITH_ALEF_MAKSURA
         | ARABIC_SMALL_HIGH_LIGATURE_QAF_WITH_LAM_WITH_ALEF_MAKSURA
@@ -63,9 +122,14 @@ pub(super) fn map(c: char) -> &'static [&'static str] {
         | ARABIC_SMALL_HIGH_JEEM
         | ARABIC_SMALL_HIGH_THREE_DOTS
         | ARABIC_SMALL_HIGH_SEEN
-        | ARABIC_PLACE_OF_SAJDAH => &[""],
+        | ARABIC_EMPTY_CENTRE_LOW_STOP
+        | ARABIC_EMPTY_CENTRE_HIGH_STOP
+        | ARABIC_ROUNDED_HIGH_STOP_WITH_FILLED_CENTRE => &[""],
 
-        _ => &[""],
+        ARABIC_TATWEEL | ARABIC_END_OF_AYAH | ARABIC_START_OF_RUB_EL_HIZB => &[""],
+        ARABIC_PLACE_OF_SAJDAH => &[""],
+
+        _ => &[],
     }
 }
 
@@ -78,7 +142,10 @@ pub(super) fn contextual_map(context: Option<char>, c: char) -> &'static [&'stat
         (Some(ARABIC_LETTER_ALEF_MAKSURA), ARABIC_LETTER_SUPERSCRIPT_ALEF) => &[""],
         (Some(ARABIC_FATHA), ARABIC_LETTER_SUPERSCRIPT_ALEF) => &[""],
         (Some(ARABIC_KASRA), ARABIC_LETTER_YEH) => &[""],
+        (Some(ARABIC_DAMMA), ARABIC_SMALL_WAW) => &[""],
         (Some(ARABIC_LETTER_ALEF), ARABIC_LETTER_LAM) => &[""],
+        (Some(ARABIC_LETTER_ALEF_WASLA), ARABIC_LETTER_LAM) => &[""],
+        (Some(ARABIC_KASRA), ARABIC_LETTER_ALEF_MAKSURA) => &[""],
         (Some(ARABIC_LETTER_REH), ARABIC_FATHA) => &["e", "ee"],
 
         (Some(cc), ARABIC_SHADDA) => map(cc),
@@ -87,6 +154,23 @@ pub(super) fn contextual_map(context: Option<char>, c: char) -> &'static [&'stat
     }
 }
 
+pub(super) fn is_harakat(c: char) -> bool {
+    matches!(
+        c,
+        ARABIC_SMALL_FATHA..=ARABIC_SMALL_KASRA
+            | ARABIC_FATHATAN..=ARABIC_SUBSCRIPT_ALEF
+            | ARABIC_LETTER_SUPERSCRIPT_ALEF
+            | ARABIC_SMALL_HIGH_ROUNDED_ZERO..=ARABIC_SMALL_HIGH_MADDA
+            | ARABIC_SMALL_HIGH_NOON
+            | ARABIC_SMALL_LOW_MEEM
+            | ARABIC_OPEN_FATHATAN..=ARABIC_OPEN_KASRATAN
+    )
+}
+
+pub(super) fn strip_harakat(s: &str) -> String {
+    s.chars().filter(|&c| !is_harakat(c)).collect()
+}
+
 pub(super) fn harf_muqottoah_map(c: char) -> &'static [&'static str] {
     match c {
         ARABIC_LETTER_ALEF => &["alif"],
@@ -107,3 +191,81 @@ pub(super) fn harf_muqottoah_map(c: char) -> &'static [&'static str] {
         _ => &[],
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AYA_COUNT, QURAN_TXT};
+
+    fn reads(quran: &str, text: &str, context: Option<char>) -> bool {
+        let mut chars = quran.chars();
+        let Some(c) = chars.next() else {
+            return text.is_empty();
+        };
+        { map(c).iter().chain(contextual_map(context, c)) }
+            .filter_map(|tsl| text.strip_prefix(tsl))
+            .any(|text| reads(chars.as_str(), text, Some(c)))
+    }
+
+    #[test]
+    fn test_map_covers_quran_simple() {
+        let ayas = QURAN_TXT.lines().take(AYA_COUNT);
+        let unmapped = ayas.flat_map(str::chars).find(|&c| map(c).is_empty());
+        assert_eq!(unmapped, None);
+    }
+
+    #[test]
+    fn test_map_covers_uthmani_marks() {
+        let marks = [
+            ARABIC_SMALL_FATHA,
+            ARABIC_SMALL_DAMMA,
+            ARABIC_SMALL_KASRA,
+            ARABIC_TATWEEL,
+            ARABIC_MADDAH_ABOVE,
+            ARABIC_HAMZA_ABOVE,
+            ARABIC_HAMZA_BELOW,
+            ARABIC_SUBSCRIPT_ALEF,
+            ARABIC_LETTER_ALEF_WASLA,
+            ARABIC_LETTER_FARSI_YEH,
+        ];
+        let marks = marks
+            .into_iter()
+            .chain(
+                ARABIC_SMALL_HIGH_LIGATURE_SAD_WITH_LAM_WITH_ALEF_MAKSURA..=ARABIC_SMALL_LOW_MEEM,
+            )
+            .chain(ARABIC_OPEN_FATHATAN..=ARABIC_OPEN_KASRATAN);
+        for c in marks {
+            assert!(!map(c).is_empty(), "U+{:04X} is not mapped", c as u32);
+        }
+        assert!(map('x').is_empty());
+    }
+
+    #[test]
+    fn test_reads_uthmani() {
+        let pairs = [
+            ("بِسْمِ ٱللَّهِ ٱلرَّحْمَٰنِ ٱلرَّحِيمِ", "bismillahirrohmanirrohimi"),
+            ("مَٰلِكِ يَوْمِ ٱلدِّينِ", "malikiyaumiddini"),
+            ("ذَٰلِكَ ٱلْكِتَٰبُ لَا رَيْبَ ۛ فِيهِ ۛ", "dzalikalkitabularoibafihi"),
+            ("إِنَّهُۥ", "innahu"),
+            ("يَسْتَحْىِۦٓ", "yastahyi"),
+            ("أَنۢبِئُونِى", "anbi'uni"),
+            ("سَمِيعٌۢ بَصِيرٌ", "sami'unbashirun"),
+            ("أُو۟لَٰٓئِكَ", "ulaika"),
+        ];
+        for (quran, text) in pairs {
+            assert!(reads(quran, text, None), "{quran} should read as {text}");
+        }
+        assert!(!reads("بِسْمِ", "basmi", None));
+    }
+
+    #[test]
+    fn test_reads_kasra_alef_maksura() {
+        // Uthmani text of aya 2:10. The contextual reading is added to the ones of `map`, and the
+        // shipped text never has alef maksura after kasra, so its encode results are unchanged.
+        let quran = "فِى قُلُوبِهِم مَّرَضٌ";
+        assert!(reads(quran, "fiqulubihimmarodun", None));
+        assert!(reads(quran, "fiiqulubihimmarodun", None));
+        assert!(reads("فِى", "fiy", None));
+        assert!(!QURAN_TXT.contains("ِى"));
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+
+use super::chars::*;
+use crate::{Quranize, SearchScope, suffix_tree::is_separator};
+
+/// How hamza forms (`أ إ آ ؤ ئ`) are counted, see [`LetterRules`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HamzaForms {
+    /// Each form is counted as a letter of its own.
+    #[default]
+    Distinct,
+    /// Every form is counted as `ء`.
+    Hamza,
+    /// Each form is counted as its carrier letter, i.e. `ا`, `و`, or `ي`.
+    Carrier,
+}
+
+/// Rules for counting letters, for [`Quranize::text_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LetterRules {
+    /// How hamza forms are counted.
+    pub hamza_forms: HamzaForms,
+    /// Count `ى` as `ي` instead of a letter of its own.
+    pub alef_maksura_as_yeh: bool,
+    /// Count the superscript alef `ٰ` as `ا` instead of leaving it out.
+    pub superscript_alef: bool,
+}
+
+/// Letter count, word count, and abjad value of a text, see [`Quranize::text_stats`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextStats {
+    /// Occurrence count of each letter, after applying [`LetterRules`].
+    pub letters: BTreeMap<char, usize>,
+    /// Number of words, i.e. parts separated by spaces and pause marks that contain a letter.
+    pub word_count: usize,
+    /// Sum of the abjad numeral values of all counted letters.
+    pub abjad: u64,
+}
+
+impl TextStats {
+    /// Count all letter occurrences.
+    pub fn letter_count(&self) -> usize {
+        self.letters.values().sum()
+    }
+
+    fn add(&mut self, other: Self) {
+        for (c, n) in other.letters {
+            *self.letters.entry(c).or_default() += n;
+        }
+        self.word_count += other.word_count;
+        self.abjad += other.abjad;
+    }
+}
+
+impl Quranize {
+    /// Count letters and words, and sum the abjad values of a quran text, e.g. an aya text from
+    /// [`get_data`](Self::get_data) or a quran form from [`encode`](Self::encode).
+    /// Harakat and other marks are not counted as letters. The alef wasla `ٱ` is counted as `ا`,
+    /// and the abjad value of `ة` is the one of `ه`, `ى` the one of `ي`, and hamza forms the one
+    /// of `ا` or their carrier letter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{HamzaForms, LetterRules, Quranize};
+    ///
+    /// let stats = Quranize::text_stats("بِسْمِ اللَّهِ الرَّحْمَٰنِ الرَّحِيمِ", Default::default());
+    /// assert_eq!((stats.letter_count(), stats.word_count, stats.abjad), (19, 4, 786));
+    /// assert_eq!(stats.letters[&'ل'], 4);
+    ///
+    /// let rules = LetterRules { hamza_forms: HamzaForms::Hamza, ..Default::default() };
+    /// assert_eq!(Quranize::text_stats("إِنَّ", rules).letters[&'ء'], 1);
+    /// ```
+    pub fn text_stats(text: &str, rules: LetterRules) -> TextStats {
+        let mut stats = TextStats::default();
+        for word in text.split(is_separator) {
+            let letters = Vec::from_iter(word.chars().filter_map(|c| counted_letter(c, rules)));
+            stats.word_count += !letters.is_empty() as usize;
+            for c in letters {
+                *stats.letters.entry(c).or_default() += 1;
+                stats.abjad += abjad_value(c);
+            }
+        }
+        stats
+    }
+
+    /// Compute [`text_stats`](Self::text_stats) over all ayas in `scope`, e.g. one aya, a sura,
+    /// or a range of ayas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::SearchScope;
+    ///
+    /// let q = quranize::Quranize::new();
+    /// let stats = q.get_stats(&SearchScope::sura(1), Default::default());
+    /// assert_eq!(stats.word_count, 29);
+    /// let stats = q.get_stats(&SearchScope::new([0..1]), Default::default());
+    /// assert_eq!(stats.letter_count(), 19);
+    /// ```
+    pub fn get_stats(&self, scope: &SearchScope, rules: LetterRules) -> TextStats {
+        let mut stats = TextStats::default();
+        for i in scope.ranges().iter().cloned().flatten() {
+            stats.add(Self::text_stats(self.data[i].3, rules));
+        }
+        stats
+    }
+}
+
+fn counted_letter(c: char, rules: LetterRules) -> Option<char> {
+    match (c, rules.hamza_forms) {
+        (
+            ARABIC_LETTER_ALEF_WITH_MADDA_ABOVE
+            | ARABIC_LETTER_ALEF_WITH_HAMZA_ABOVE
+            | ARABIC_LETTER_WAW_WITH_HAMZA_ABOVE
+            | ARABIC_LETTER_ALEF_WITH_HAMZA_BELOW
+            | ARABIC_LETTER_YEH_WITH_HAMZA_ABOVE,
+            HamzaForms::Distinct,
+        ) => Some(c),
+        (
+            ARABIC_LETTER_ALEF_WITH_MADDA_ABOVE
+            | ARABIC_LETTER_ALEF_WITH_HAMZA_ABOVE
+            | ARABIC_LETTER_WAW_WITH_HAMZA_ABOVE
+            | ARABIC_LETTER_ALEF_WITH_HAMZA_BELOW
+            | ARABIC_LETTER_YEH_WITH_HAMZA_ABOVE,
+            HamzaForms::Hamza,
+        ) => Some(ARABIC_LETTER_HAMZA),
+        (
+            ARABIC_LETTER_ALEF_WITH_MADDA_ABOVE
+            | ARABIC_LETTER_ALEF_WITH_HAMZA_ABOVE
+            | ARABIC_LETTER_ALEF_WITH_HAMZA_BELOW,
+            HamzaForms::Carrier,
+        ) => Some(ARABIC_LETTER_ALEF),
+        (ARABIC_LETTER_WAW_WITH_HAMZA_ABOVE, HamzaForms::Carrier) => Some(ARABIC_LETTER_WAW),
+        (ARABIC_LETTER_YEH_WITH_HAMZA_ABOVE, HamzaForms::Carrier) => Some(ARABIC_LETTER_YEH),
+        (ARABIC_LETTER_ALEF_MAKSURA, _) if rules.alef_maksura_as_yeh => Some(ARABIC_LETTER_YEH),
+        (ARABIC_LETTER_SUPERSCRIPT_ALEF, _) if rules.superscript_alef => Some(ARABIC_LETTER_ALEF),
+        (ARABIC_LETTER_ALEF_WASLA, _) => Some(ARABIC_LETTER_ALEF),
+        (ARABIC_LETTER_FARSI_YEH, _) => Some(ARABIC_LETTER_YEH),
+        (ARABIC_LETTER_HAMZA..=ARABIC_LETTER_GHAIN | ARABIC_LETTER_FEH..=ARABIC_LETTER_YEH, _) => {
+            Some(c)
+        }
+        _ => None,
+    }
+}
+
+fn abjad_value(c: char) -> u64 {
+    match c {
+        ARABIC_LETTER_HAMZA
+        | ARABIC_LETTER_ALEF_WITH_MADDA_ABOVE
+        | ARABIC_LETTER_ALEF_WITH_HAMZA_ABOVE
+        | ARABIC_LETTER_ALEF_WITH_HAMZA_BELOW
+        | ARABIC_LETTER_ALEF => 1,
+        ARABIC_LETTER_BEH => 2,
+        ARABIC_LETTER_JEEM => 3,
+        ARABIC_LETTER_DAL => 4,
+        ARABIC_LETTER_HEH | ARABIC_LETTER_TEH_MARBUTA => 5,
+        ARABIC_LETTER_WAW | ARABIC_LETTER_WAW_WITH_HAMZA_ABOVE => 6,
+        ARABIC_LETTER_ZAIN => 7,
+        ARABIC_LETTER_HAH => 8,
+        ARABIC_LETTER_TAH => 9,
+        ARABIC_LETTER_YEH | ARABIC_LETTER_YEH_WITH_HAMZA_ABOVE | ARABIC_LETTER_ALEF_MAKSURA => 10,
+        ARABIC_LETTER_KAF => 20,
+        ARABIC_LETTER_LAM => 30,
+        ARABIC_LETTER_MEEM => 40,
+        ARABIC_LETTER_NOON => 50,
+        ARABIC_LETTER_SEEN => 60,
+        ARABIC_LETTER_AIN => 70,
+        ARABIC_LETTER_FEH => 80,
+        ARABIC_LETTER_SAD => 90,
+        ARABIC_LETTER_QAF => 100,
+        ARABIC_LETTER_REH => 200,
+        ARABIC_LETTER_SHEEN => 300,
+        ARABIC_LETTER_TEH => 400,
+        ARABIC_LETTER_THEH => 500,
+        ARABIC_LETTER_KHAH => 600,
+        ARABIC_LETTER_THAL => 700,
+        ARABIC_LETTER_DAD => 800,
+        ARABIC_LETTER_ZAH => 900,
+        ARABIC_LETTER_GHAIN => 1000,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_counted_letter() {
+        let rules = LetterRules::default();
+        assert_eq!(counted_letter('أ', rules), Some('أ'));
+        assert_eq!(counted_letter('ٱ', rules), Some('ا'));
+        assert_eq!(counted_letter('ٰ', rules), None);
+        assert_eq!(counted_letter('ى', rules), Some('ى'));
+        assert_eq!(counted_letter('َ', rules), None);
+        assert_eq!(counted_letter('ـ', rules), None);
+        assert_eq!(counted_letter('ۛ', rules), None);
+
+        let rules = LetterRules {
+            hamza_forms: HamzaForms::Carrier,
+            alef_maksura_as_yeh: true,
+            superscript_alef: true,
+        };
+        assert_eq!(counted_letter('ؤ', rules), Some('و'));
+        assert_eq!(counted_letter('ئ', rules), Some('ي'));
+        assert_eq!(counted_letter('آ', rules), Some('ا'));
+        assert_eq!(counted_letter('ٰ', rules), Some('ا'));
+        assert_eq!(counted_letter('ى', rules), Some('ي'));
+    }
+
+    #[test]
+    fn test_text_stats() {
+        let text = "ذَٰلِكَ الْكِتَابُ لَا رَيْبَ ۛ فِيهِ ۛ هُدًى لِّلْمُتَّقِينَ";
+        let stats = Quranize::text_stats(text, Default::default());
+        assert_eq!(stats.word_count, 7);
+        assert_eq!(stats.letter_count(), 27);
+        let rules = LetterRules {
+            superscript_alef: true,
+            ..Default::default()
+        };
+        let with_superscript = Quranize::text_stats(text, rules);
+        assert_eq!(with_superscript.letter_count(), 28);
+        assert_eq!(with_superscript.abjad, stats.abjad + 1);
+        assert_eq!(Quranize::text_stats("", rules), TextStats::default());
+        assert_eq!(Quranize::text_stats("يَسْجُدُونَ ۩", rules).word_count, 1);
+    }
+
+    #[test]
+    fn test_get_stats() {
+        let q = Quranize::new();
+        let rules = LetterRules::default();
+        let sura = q.get_stats(&SearchScope::sura(112), rules);
+        let ayas = (6221..6225).map(|i| q.get_stats(&SearchScope::new(Some(i..i + 1)), rules));
+        let mut sum = TextStats::default();
+        ayas.for_each(|stats| sum.add(stats));
+        assert_eq!(sura, sum);
+        assert_eq!(sura.word_count, 15);
+        assert_eq!(
+            q.get_stats(&SearchScope::new([]), rules),
+            TextStats::default()
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::ops::Range;
+
+use super::{chars::*, is_harakat};
+use crate::{Quranize, suffix_tree::is_separator};
+
+/// Tajweed rule of an annotated span, see [`Quranize::tajweed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TajweedRule {
+    /// Nasal sound of a noon or meem with shadda.
+    Ghunnah,
+    /// Echoing sound of `ق ط ب ج د` with sukun, or at the end of the text.
+    Qalqalah,
+    /// Silent lam of the definite article before a sun letter.
+    LamShamsiyyah,
+    /// Noon sakinah or tanween merged into the next word starting with `ي ن م و`.
+    IdghamBighunnah,
+    /// Noon sakinah or tanween merged into the next word starting with `ل ر`.
+    IdghamBilaGhunnah,
+    /// Noon sakinah or tanween hidden before one of the fifteen ikhfa' letters.
+    Ikhfa,
+    /// Noon sakinah or tanween read as meem before `ب`.
+    Iqlab,
+    /// Meem sakinah hidden before `ب`.
+    IkhfaSyafawi,
+    /// Meem sakinah merged into the next meem.
+    IdghamMimi,
+    /// Natural madd of two harakat.
+    MaddThabii,
+    /// Madd followed by hamza in the same word.
+    MaddWajibMuttasil,
+    /// Madd at the end of a word, followed by hamza at the start of the next word.
+    MaddJaizMunfasil,
+    /// Madd followed by a sukun or shadda in the same word.
+    MaddLazim,
+    /// Madd before the last letter of the text, where the reading stops.
+    MaddAridLissukun,
+}
+
+struct Unit {
+    c: char,
+    range: Range<usize>,
+    marks: Vec<char>,
+    word: usize,
+}
+
+impl Unit {
+    fn has(&self, mark: char) -> bool {
+        self.marks.contains(&mark)
+    }
+
+    fn is_sakin(&self) -> bool {
+        self.marks.is_empty() || self.marks == [ARABIC_SUKUN]
+    }
+
+    fn has_tanween(&self) -> bool {
+        self.has(ARABIC_FATHATAN) || self.has(ARABIC_DAMMATAN) || self.has(ARABIC_KASRATAN)
+    }
+}
+
+impl Quranize {
+    /// Annotate tajweed rules in a quran text, e.g. an aya text from
+    /// [`get_data`](Self::get_data) or a quran form from [`encode`](Self::encode),
+    /// returning a list of tuple, sorted by position:
+    /// - `Range<usize>`: byte range of the annotated span in `text`
+    /// - `TajweedRule`: the rule applied to the span
+    ///
+    /// Rules between words are detected across spaces and pause marks.
+    /// The end of `text` is read as a stop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{Quranize, TajweedRule};
+    ///
+    /// let text = "مِن شَرِّ الْوَسْوَاسِ الْخَنَّاسِ";
+    /// let rules = Quranize::tajweed(text);
+    /// assert_eq!(rules[0], (4..11, TajweedRule::Ikhfa));
+    /// assert!(rules.iter().any(|(r, t)| (&text[r.clone()], *t) == ("نَّ", TajweedRule::Ghunnah)));
+    /// ```
+    pub fn tajweed(text: &str) -> Vec<(Range<usize>, TajweedRule)> {
+        let units = units(text);
+        let mut rules = Vec::from_iter((0..units.len()).flat_map(|k| unit_rules(&units, k)));
+        rules.sort_by_key(|(r, t)| (r.start, r.end, *t));
+        rules
+    }
+
+    /// Annotate tajweed rules in aya row `i` (`0..6236`) like [`tajweed`](Self::tajweed).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::TajweedRule;
+    ///
+    /// let q = quranize::Quranize::new();
+    /// let rules = q.get_tajweed(6235).unwrap();
+    /// assert_eq!(rules.last().map(|(_, t)| *t), Some(TajweedRule::MaddAridLissukun));
+    /// assert_eq!(q.get_tajweed(6236), None);
+    /// ```
+    pub fn get_tajweed(&self, i: usize) -> Option<Vec<(Range<usize>, TajweedRule)>> {
+        self.data.get(i).map(|&(_, _, _, text)| Self::tajweed(text))
+    }
+}
+
+fn units(text: &str) -> Vec<Unit> {
+    let mut units = Vec::<Unit>::new();
+    let mut word = 0;
+    for (j, c) in text.char_indices() {
+        let last_word = units.last().map(|u| u.word);
+        match (is_separator(c), units.last_mut()) {
+            (true, _) => word += (last_word == Some(word)) as usize,
+            (false, Some(u)) if is_harakat(c) => {
+                u.marks.push(c);
+                u.range.end = j + c.len_utf8();
+            }
+            (false, _) => units.push(Unit {
+                c,
+                range: j..j + c.len_utf8(),
+                marks: vec![],
+                word,
+            }),
+        }
+    }
+    units
+}
+
+fn unit_rules(units: &[Unit], k: usize) -> Vec<(Range<usize>, TajweedRule)> {
+    let u = &units[k];
+    let is_last = k + 1 == units.len();
+    let next = units.get(k + 1);
+    let span_to = |v: &Unit| u.range.start..v.range.end;
+    let mut rules = vec![];
+
+    if matches!(u.c, ARABIC_LETTER_NOON | ARABIC_LETTER_MEEM) && u.has(ARABIC_SHADDA) {
+        rules.push((u.range.clone(), TajweedRule::Ghunnah));
+    }
+    if is_qalqalah_letter(u.c) && (u.has(ARABIC_SUKUN) || is_last) {
+        rules.push((u.range.clone(), TajweedRule::Qalqalah));
+    }
+    if u.c == ARABIC_LETTER_LAM && u.marks.is_empty() && k > 0 {
+        let after_alef = matches!(
+            units[k - 1].c,
+            ARABIC_LETTER_ALEF | ARABIC_LETTER_ALEF_WASLA
+        );
+        let before_sun =
+            next.is_some_and(|v| v.word == u.word && is_sun_letter(v.c) && v.has(ARABIC_SHADDA));
+        if after_alef && units[k - 1].word == u.word && before_sun {
+            rules.push((u.range.clone(), TajweedRule::LamShamsiyyah));
+        }
+    }
+
+    let nun_sakinah = u.c == ARABIC_LETTER_NOON && u.is_sakin() && !is_last;
+    if nun_sakinah || u.has_tanween() {
+        let silent_alef = |v: &&Unit| {
+            u.has(ARABIC_FATHATAN)
+                && matches!(v.c, ARABIC_LETTER_ALEF | ARABIC_LETTER_ALEF_MAKSURA)
+                && v.marks.is_empty()
+        };
+        let v = units[k + 1..].iter().find(|v| !silent_alef(v));
+        let rule = v.and_then(|v| match v.c {
+            ARABIC_LETTER_YEH | ARABIC_LETTER_NOON | ARABIC_LETTER_MEEM | ARABIC_LETTER_WAW
+                if v.word != u.word =>
+            {
+                Some(TajweedRule::IdghamBighunnah)
+            }
+            ARABIC_LETTER_LAM | ARABIC_LETTER_REH if v.word != u.word => {
+                Some(TajweedRule::IdghamBilaGhunnah)
+            }
+            ARABIC_LETTER_BEH => Some(TajweedRule::Iqlab),
+            c if is_ikhfa_letter(c) => Some(TajweedRule::Ikhfa),
+            _ => None,
+        });
+        rules.extend(v.zip(rule).map(|(v, rule)| (span_to(v), rule)));
+    }
+
+    if u.c == ARABIC_LETTER_MEEM && u.is_sakin() {
+        let rule = next.and_then(|v| match v.c {
+            ARABIC_LETTER_BEH => Some(TajweedRule::IkhfaSyafawi),
+            ARABIC_LETTER_MEEM => Some(TajweedRule::IdghamMimi),
+            _ => None,
+        });
+        rules.extend(next.zip(rule).map(|(v, rule)| (span_to(v), rule)));
+    }
+
+    let prev = k.checked_sub(1).map(|k| &units[k]);
+    let madd_letter = prev.filter(|p| p.word == u.word).filter(|p| match u.c {
+        ARABIC_LETTER_ALEF => u.marks.is_empty() && p.has(ARABIC_FATHA) && !is_article(units, k),
+        ARABIC_LETTER_WAW => u.is_sakin() && p.has(ARABIC_DAMMA),
+        ARABIC_LETTER_YEH => u.is_sakin() && p.has(ARABIC_KASRA),
+        _ => false,
+    });
+    let madd = match (madd_letter, u.has(ARABIC_LETTER_SUPERSCRIPT_ALEF)) {
+        (Some(p), _) => Some(p.range.start..u.range.end),
+        (None, true) => Some(u.range.clone()),
+        (None, false) => None,
+    };
+    if let Some(span) = madd {
+        let rule = match next {
+            Some(v) if is_hamza(v.c) && v.word == u.word => TajweedRule::MaddWajibMuttasil,
+            Some(v) if is_hamza(v.c) => TajweedRule::MaddJaizMunfasil,
+            Some(v) if v.word == u.word && (v.has(ARABIC_SHADDA) || v.has(ARABIC_SUKUN)) => {
+                TajweedRule::MaddLazim
+            }
+            Some(v) if v.word == u.word && k + 2 == units.len() => TajweedRule::MaddAridLissukun,
+            _ => TajweedRule::MaddThabii,
+        };
+        rules.push((span, rule));
+    }
+    rules
+}
+
+fn is_article(units: &[Unit], k: usize) -> bool {
+    let u = &units[k];
+    let word_start = units[..k]
+        .iter()
+        .rposition(|v| v.word != u.word)
+        .map_or(0, |p| p + 1);
+    let prefix = &units[word_start..k];
+    let unvoweled_lam = units.get(k + 1).is_some_and(|v| {
+        v.c == ARABIC_LETTER_LAM && v.word == u.word && (v.is_sakin() || v.has(ARABIC_SHADDA))
+    });
+    let prefixed = match prefix {
+        [] => true,
+        [p] => matches!(
+            p.c,
+            ARABIC_LETTER_WAW | ARABIC_LETTER_FEH | ARABIC_LETTER_BEH | ARABIC_LETTER_KAF
+        ),
+        _ => false,
+    };
+    u.c == ARABIC_LETTER_ALEF && unvoweled_lam && prefixed
+}
+
+fn is_qalqalah_letter(c: char) -> bool {
+    matches!(
+        c,
+        ARABIC_LETTER_QAF
+            | ARABIC_LETTER_TAH
+            | ARABIC_LETTER_BEH
+            | ARABIC_LETTER_JEEM
+            | ARABIC_LETTER_DAL
+    )
+}
+
+fn is_sun_letter(c: char) -> bool {
+    matches!(
+        c,
+        ARABIC_LETTER_TEH..=ARABIC_LETTER_THEH
+            | ARABIC_LETTER_DAL..=ARABIC_LETTER_ZAH
+            | ARABIC_LETTER_LAM
+            | ARABIC_LETTER_NOON
+    )
+}
+
+fn is_ikhfa_letter(c: char) -> bool {
+    matches!(
+        c,
+        ARABIC_LETTER_TEH..=ARABIC_LETTER_JEEM
+            | ARABIC_LETTER_DAL..=ARABIC_LETTER_ZAH
+            | ARABIC_LETTER_FEH..=ARABIC_LETTER_KAF
+    ) && c != ARABIC_LETTER_REH
+}
+
+fn is_hamza(c: char) -> bool {
+    matches!(c, ARABIC_LETTER_HAMZA..=ARABIC_LETTER_YEH_WITH_HAMZA_ABOVE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TajweedRule::*;
+    use pretty_assertions::assert_eq;
+
+    fn rules(text: &str) -> Vec<(&str, TajweedRule)> {
+        { Quranize::tajweed(text).into_iter() }
+            .map(|(r, t)| (&text[r], t))
+            .collect()
+    }
+
+    #[test]
+    fn test_units() {
+        let units = units("مِنْ ۚ قَبْلِ");
+        let units = Vec::from_iter(units.iter().map(|u| (u.c, u.range.clone(), u.word)));
+        assert_eq!(units[0], ('م', 0..4, 0));
+        assert_eq!(units[2], ('ق', 12..16, 1));
+        assert!(self::units("").is_empty());
+    }
+
+    #[test]
+    fn test_tajweed() {
+        assert_eq!(rules("مِن شَرِّ"), [("ن شَ", Ikhfa)]);
+        assert_eq!(rules("مِن بَعْدِ"), [("ن بَ", Iqlab), ("دِ", Qalqalah)]);
+        assert_eq!(
+            rules("مَن يَقُولُ"),
+            [("ن يَ", IdghamBighunnah), ("قُو", MaddAridLissukun)]
+        );
+        assert_eq!(rules("هُدًى لِّلْمُتَّقِينَ")[0], ("دًى لِّ", IdghamBilaGhunnah));
+        assert_eq!(rules("هُم بِهِ"), [("م بِ", IkhfaSyafawi)]);
+        assert_eq!(
+            rules("لَهُم مَّا"),
+            [("م مَّ", IdghamMimi), ("مَّ", Ghunnah), ("مَّا", MaddThabii)]
+        );
+        assert_eq!(rules("أَحَدٌ"), [("دٌ", Qalqalah)]);
+        assert_eq!(rules("عَمَّ"), [("مَّ", Ghunnah)]);
+        assert_eq!(
+            rules("يَتَسَاءَلُونَ"),
+            [("سَا", MaddWajibMuttasil), ("لُو", MaddAridLissukun)]
+        );
+        assert_eq!(rules("إِلَّا أَنفُسَهُمْ"), [("لَّا", MaddJaizMunfasil), ("نفُ", Ikhfa)]);
+        assert_eq!(
+            rules("الضَّالِّينَ"),
+            [
+                ("ل", LamShamsiyyah),
+                ("ضَّا", MaddLazim),
+                ("لِّي", MaddAridLissukun)
+            ]
+        );
+        assert_eq!(rules("وَالَّذِينَ"), [("ذِي", MaddAridLissukun)]);
+        assert!(rules("").is_empty());
+    }
+
+    #[test]
+    fn test_get_tajweed() {
+        let q = Quranize::new();
+        for i in 0..q.data.len() {
+            let text = q.data[i].3;
+            for (r, _) in q.get_tajweed(i).unwrap() {
+                assert!(text.get(r).is_some_and(|s| !s.is_empty()));
+            }
+        }
+    }
+}
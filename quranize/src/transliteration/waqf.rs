@@ -0,0 +1,208 @@
+use std::borrow::Cow;
+
+use super::{Reading, chars::*};
+use crate::{EncodeResults, Quranize, SearchScope, budget::Tracker, encode_iter::EncodeIter};
+
+/// Waqf (pause) mark of a quran text, see [`Quranize::waqf_marks`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WaqfMark {
+    /// `ۘ`, stopping is necessary.
+    Lazim,
+    /// `ۚ`, stopping and continuing are equally permissible.
+    Jaiz,
+    /// `ۗ`, stopping is preferred.
+    Qila,
+    /// `ۛ`, stopping at one of a pair of these marks, but not at both.
+    Muanaqah,
+    /// `ۖ`, continuing is preferred.
+    WaslAula,
+    /// `ۙ`, stopping is not allowed.
+    Mamnu,
+    /// `ۜ`, a short pause without taking a breath.
+    Saktah,
+}
+
+impl WaqfMark {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            ARABIC_SMALL_HIGH_MEEM_INITIAL_FORM => Some(Self::Lazim),
+            ARABIC_SMALL_HIGH_JEEM => Some(Self::Jaiz),
+            ARABIC_SMALL_HIGH_LIGATURE_QAF_WITH_LAM_WITH_ALEF_MAKSURA => Some(Self::Qila),
+            ARABIC_SMALL_HIGH_THREE_DOTS => Some(Self::Muanaqah),
+            ARABIC_SMALL_HIGH_LIGATURE_SAD_WITH_LAM_WITH_ALEF_MAKSURA => Some(Self::WaslAula),
+            ARABIC_SMALL_HIGH_LAM_ALEF => Some(Self::Mamnu),
+            ARABIC_SMALL_HIGH_SEEN => Some(Self::Saktah),
+            _ => None,
+        }
+    }
+
+    /// Check whether the reading may stop at this mark, i.e. every mark except [`Mamnu`](Self::Mamnu).
+    pub fn is_stop(self) -> bool {
+        self != Self::Mamnu
+    }
+}
+
+impl Quranize {
+    /// Get waqf marks in a quran text, returning a list of tuple, sorted by position:
+    /// - `usize`: byte offset of the mark in `text`
+    /// - `WaqfMark`: type of the mark
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::{Quranize, WaqfMark};
+    ///
+    /// let text = "ذَٰلِكَ الْكِتَابُ لَا رَيْبَ ۛ فِيهِ ۛ هُدًى لِّلْمُتَّقِينَ";
+    /// let marks = Quranize::waqf_marks(text);
+    /// assert_eq!(marks.len(), 2);
+    /// assert_eq!(marks[0].1, WaqfMark::Muanaqah);
+    /// assert!(text[marks[0].0..].starts_with("ۛ فِيهِ"));
+    /// ```
+    pub fn waqf_marks(text: &str) -> Vec<(usize, WaqfMark)> {
+        { text.char_indices() }
+            .filter_map(|(j, c)| WaqfMark::from_char(c).map(|m| (j, m)))
+            .collect()
+    }
+
+    /// Get waqf marks in aya row `i` (`0..6236`) like [`waqf_marks`](Self::waqf_marks).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quranize::WaqfMark;
+    ///
+    /// let q = quranize::Quranize::new();
+    /// let marks = q.get_waqf_marks(13).unwrap();
+    /// assert_eq!(Vec::from_iter(marks.iter().map(|&(_, m)| m)), [WaqfMark::WaslAula; 2]);
+    /// assert_eq!(q.get_waqf_marks(0), Some(vec![]));
+    /// assert_eq!(q.get_waqf_marks(6236), None);
+    /// ```
+    pub fn get_waqf_marks(&self, i: usize) -> Option<Vec<(usize, WaqfMark)>> {
+        self.data
+            .get(i)
+            .map(|&(_, _, _, text)| Self::waqf_marks(text))
+    }
+
+    /// Do transliteration on `s` like [`encode`](Self::encode), but also accept words read in
+    /// pause form, e.g. `"fih"` for `"فِيهِ"`, `"hudaa"` for `"هُدًى"`, or `"ghisyawah"` for
+    /// `"غِشَاوَةٌ"`. A pause form is only accepted where the reading may stop, i.e. before a
+    /// waqf mark other than [`WaqfMark::Mamnu`] or at the end of an aya, and only locations
+    /// where such a stop exists are counted.
+    ///
+    /// Readings already accepted by [`encode`](Self::encode) are not restricted to stops, e.g.
+    /// `ة` read as `"h"` with a silent fathatan, so `"rohmah wa"` matches `"رَحْمَةً وَ"` in the
+    /// middle of an aya, while `"syafaah wala"` does not match `"شَفَاعَةٌ وَلَا"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// assert!(q.encode("la roib fihi hudan").is_empty());
+    /// let (quran, count, _) = &q.encode_at_pause("la roib fihi hudan")[0];
+    /// assert_eq!((quran.as_str(), *count), ("لَا رَيْبَ ۛ فِيهِ ۛ هُدً", 1));
+    /// assert!(q.encode_at_pause("alhamd lillah").is_empty());
+    /// ```
+    pub fn encode_at_pause(&self, s: &str) -> EncodeResults {
+        let (key, readings) = (Self::normalize_key(s), [Reading::Pause(None, false)]);
+        let scope = Cow::Owned(SearchScope::all());
+        let paused = EncodeIter::new(self, key, &readings, Tracker::unlimited(), scope);
+        let mut results = self.encode(s);
+        results.extend(paused.map(|(q, (_, n), e, _)| (q, n, Self::strip_context(e))));
+        results.sort_by(|x, y| x.0.cmp(&y.0).then(y.1.cmp(&x.1)));
+        results.dedup_by(|x, y| x.0 == y.0);
+        results
+    }
+
+    /// Count locations in `sc` under vertex `w` where the text after `l`, the rest of the
+    /// current edge, reaches a stop right after the harakat `p` read in pause form.
+    pub(crate) fn count_at_pause(&self, w: usize, l: &str, mut p: char, sc: &SearchScope) -> usize {
+        for c in l.chars() {
+            match c {
+                LINE_FEED => return self.tree.count_data_in(w, sc),
+                c => match pause_tail(p, c) {
+                    Some(None) => return self.tree.count_data_in(w, sc),
+                    Some(Some(next)) => p = next,
+                    None => return 0,
+                },
+            }
+        }
+        { self.tree.edges_from(w) }
+            .map(|&(_, x, l)| self.count_at_pause(x, l, p, sc))
+            .sum()
+    }
+}
+
+/// Pause form of harakat `c` at the end of a word, given the previous char `context`.
+pub(super) fn pause_map(context: Option<char>, c: char) -> &'static [&'static str] {
+    match (context, c) {
+        (Some(ARABIC_LETTER_TEH_MARBUTA), ARABIC_FATHATAN) => &[""],
+        (_, ARABIC_FATHATAN) => &["a"],
+        (_, ARABIC_DAMMATAN | ARABIC_KASRATAN) => &[""],
+        (_, ARABIC_FATHA | ARABIC_DAMMA | ARABIC_KASRA) => &[""],
+        _ => &[],
+    }
+}
+
+/// Read char `c` between a pause form and its stop, where `p` is the last char read so far.
+/// Returns `Some(None)` if the stop is reached, `Some(Some(p))` if it is still ahead,
+/// or `None` if there is no stop.
+pub(super) fn pause_tail(p: char, c: char) -> Option<Option<char>> {
+    match (p, c) {
+        (ARABIC_FATHATAN, ARABIC_LETTER_ALEF | ARABIC_LETTER_ALEF_MAKSURA) => Some(Some(c)),
+        (_, SPACE) => Some(Some(SPACE)),
+        (SPACE, c) => WaqfMark::from_char(c).filter(|m| m.is_stop()).map(|_| None),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_waqf_marks() {
+        let text = "قَالُوا يَا وَيْلَنَا مَن بَعَثَنَا مِن مَّرْقَدِنَا ۜ ۗ هَٰذَا";
+        let marks = Vec::from_iter(Quranize::waqf_marks(text).into_iter().map(|(_, m)| m));
+        assert_eq!(marks, [WaqfMark::Saktah, WaqfMark::Qila]);
+        assert!(Quranize::waqf_marks("بِسْمِ اللَّهِ").is_empty());
+
+        let q = Quranize::new();
+        let marks = q.get_waqf_marks(32).unwrap();
+        assert_eq!(marks[0].1, WaqfMark::Jaiz);
+        assert_eq!(marks[1].1, WaqfMark::WaslAula);
+        assert_eq!(marks[2].1, WaqfMark::Lazim);
+        assert!(q.data[32].3[..marks[2].0].ends_with("مَثَلًا "));
+        assert!(!WaqfMark::Mamnu.is_stop() && WaqfMark::Lazim.is_stop());
+    }
+
+    #[test]
+    fn test_pause_tail() {
+        assert_eq!(pause_tail(ARABIC_KASRA, SPACE), Some(Some(SPACE)));
+        assert_eq!(pause_tail(SPACE, ARABIC_SMALL_HIGH_JEEM), Some(None));
+        assert_eq!(pause_tail(SPACE, ARABIC_SMALL_HIGH_LAM_ALEF), None);
+        assert_eq!(pause_tail(SPACE, ARABIC_LETTER_BEH), None);
+        assert_eq!(
+            pause_tail(ARABIC_FATHATAN, ARABIC_LETTER_ALEF),
+            Some(Some(ARABIC_LETTER_ALEF))
+        );
+        assert_eq!(pause_tail(ARABIC_FATHA, ARABIC_LETTER_ALEF), None);
+    }
+
+    #[test]
+    fn test_encode_at_pause() {
+        let q = Quranize::new();
+        let quran_forms = |s| Vec::from_iter(q.encode_at_pause(s).into_iter().map(|(q, _, _)| q));
+        assert!(quran_forms("ghisyawah walahum").contains(&"غِشَاوَةٌ ۖ وَلَهُم".to_string()));
+        assert!(quran_forms("matsala yudhillu").contains(&"مَثَلًا ۘ يُضِلُّ".to_string()));
+        assert!(quran_forms("minal ilm ma laka").is_empty());
+        assert!(quran_forms("syafaah wala").is_empty());
+        assert!(!quran_forms("syafaatun wala").is_empty());
+        assert!(quran_forms("rohmah wa").contains(&"رَحْمَةً وَ".to_string()));
+        assert_eq!(q.encode_at_pause("rohmah wa"), q.encode("rohmah wa"));
+
+        let results = q.encode_at_pause("robbil alamin");
+        assert_eq!(results, q.encode("robbil alamin"));
+        assert!(q.encode_at_pause("").is_empty());
+    }
+}
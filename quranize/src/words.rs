@@ -0,0 +1,214 @@
+use std::ops::Range;
+
+use crate::{
+    Quranize,
+    quran_metadata::{AYA_COUNT, SURA_STARTS},
+    suffix_tree::{Index, is_separator},
+};
+
+/// Word location in Alquran: sura number, aya number, and word number, all starting from 1.
+///
+/// Words are numbered as in the Uthmani script, like in the Quranic Arabic Corpus and in
+/// recitation timing data, so the vocative "يَا" and "هَا" of "هَا أَنتُمْ" are part of
+/// the next word, e.g. "يَا أَيُّهَا" is word 1 of 2:21.
+pub type WordLocation = (u8, u16, u16);
+
+impl Quranize {
+    /// Get the location of the word containing location `(i, j)`, e.g. from [`find`](Self::find),
+    /// for looking it up in word-by-word data. A location between words belongs to the word
+    /// before it. Returns `None` if `(i, j)` is out of range or
+    /// inside a prefixed basmalah.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (i, j) = q.find_sorted("الرَّحِيمِ")[0];
+    /// assert_eq!(q.get_word_location((i, j)), Some((1, 1, 4)));
+    /// assert_eq!(q.get_word_location((6236, 0)), None);
+    /// ```
+    pub fn get_word_location(&self, (i, j): Index) -> Option<WordLocation> {
+        let &(_, sura, aya, text) = self.data.get(i)?;
+        let start = Self::basmalah_len(sura, aya, text) * self.basmalah as usize;
+        text.get(start..j)?;
+        let word = { numbered_words(text, start).into_iter() }
+            .take_while(|(r, _)| r.start <= j)
+            .last()
+            .map_or(1, |(_, word)| word);
+        Some((sura, aya, word))
+    }
+
+    /// Get the location `(i, j)` of the word at `location`, the inverse of
+    /// [`get_word_location`](Self::get_word_location).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let q = quranize::Quranize::new();
+    /// let (i, j) = q.get_word_index((1, 1, 4)).unwrap();
+    /// assert_eq!(&q.get_data(i).unwrap().3[j..], "الرَّحِيمِ");
+    /// assert_eq!(q.get_word_index((1, 8, 1)), None);
+    /// ```
+    pub fn get_word_index(&self, location: WordLocation) -> Option<Index> {
+        let (i, r) = self.get_word_range(location)?;
+        Some((i, r.start))
+    }
+
+    /// Get the aya row and the byte range of the word at `location`, which spans more than one
+    /// word of this crate's texts for words merged in the Uthmani script, e.g. "يَا أَيُّهَا".
+    pub(crate) fn get_word_range(
+        &self,
+        (sura, aya, word): WordLocation,
+    ) -> Option<(usize, Range<usize>)> {
+        let i = aya_row(sura, aya)?;
+        let text = self.data[i].3;
+        let start = Self::basmalah_len(sura, aya, text) * self.basmalah as usize;
+        let mut ranges = { numbered_words(text, start).into_iter() }
+            .filter(|&(_, w)| w == word)
+            .map(|(r, _)| r);
+        let first = ranges.next()?;
+        let end = ranges.next_back().map_or(first.end, |r| r.end);
+        Some((i, first.start..end))
+    }
+}
+
+/// Get the words of `text` from byte `start` with their byte ranges and their Uthmani word
+/// numbers, skipping signs like "۩" that are not words.
+fn numbered_words(text: &str, start: usize) -> Vec<(Range<usize>, u16)> {
+    let words = Vec::from_iter(
+        { words_with_ranges(&text[start..]).into_iter() }
+            .filter(|(_, w)| w.chars().any(char::is_alphabetic)),
+    );
+    let mut number = 1;
+    let mut numbered = Vec::with_capacity(words.len());
+    for (k, (r, w)) in words.iter().enumerate() {
+        numbered.push((start + r.start..start + r.end, number));
+        let prev = k.checked_sub(1).map(|k| words[k].1);
+        let next = words.get(k + 1).map(|(_, w)| *w);
+        if next.is_none_or(|next| !joins_next(prev, w, next)) {
+            number += 1;
+        }
+    }
+    numbered
+}
+
+/// Whether `word`, between `prev` and `next`, is written together with `next` in the Uthmani
+/// script: the vocative "يَا", "هَا" of "هَا أَنتُمْ", and "يَا ابْنَ أُمَّ" of 20:94.
+fn joins_next(prev: Option<&str>, word: &str, next: &str) -> bool {
+    matches!(
+        (prev, word, next),
+        (_, "يَا" | "وَيَا", _) | (_, "هَا", "أَنتُمْ") | (Some("يَا"), "ابْنَ", "أُمَّ")
+    )
+}
+
+/// Get the words of `text` with their byte ranges, without separators and pause marks.
+pub(crate) fn words_with_ranges(text: &str) -> Vec<(Range<usize>, &str)> {
+    let separators = text
+        .match_indices(is_separator)
+        .map(|(j, c)| (j, j + c.len()));
+    let bounds = Vec::from_iter(
+        [(0, 0)]
+            .into_iter()
+            .chain(separators)
+            .chain([(text.len(), text.len())]),
+    );
+    { bounds.windows(2) }
+        .map(|w| w[0].1..w[1].0)
+        .filter(|r| !r.is_empty())
+        .map(|r| (r.clone(), &text[r]))
+        .collect()
+}
+
+/// Get the aya row/// Get the aya row (`0..6236`) of aya number `aya` in sura number `sura`.
+pub(crate) fn aya_row(sura: u8, aya: u16) -> Option<usize> {
+    let start = *SURA_STARTS.get((sura as usize).checked_sub(1)?)?;
+    let end = SURA_STARTS.get(sura as usize).copied().unwrap_or(AYA_COUNT);
+    Some(start + (aya as usize).checked_sub(1)?).filter(|&i| i < end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_aya_row() {
+        assert_eq!(aya_row(1, 1), Some(0));
+        assert_eq!(aya_row(1, 7), Some(6));
+        assert_eq!(aya_row(1, 8), None);
+        assert_eq!(aya_row(114, 6), Some(AYA_COUNT - 1));
+        assert_eq!(aya_row(114, 7), None);
+        assert_eq!(aya_row(0, 1), None);
+        assert_eq!(aya_row(2, 0), None);
+    }
+
+    #[test]
+    fn test_words_with_ranges() {
+        let words = words_with_ranges("ab cd ۖ ef");
+        assert_eq!(words, [(0..2, "ab"), (3..5, "cd"), (9..11, "ef")]);
+        assert!(words_with_ranges("").is_empty());
+    }
+
+    #[test]
+    fn test_get_word_location() {
+        let q = Quranize::new();
+        let (i, j) = q.find_sorted("لَا رَيْبَ")[0];
+        assert_eq!(q.get_word_location((i, j)), Some((2, 2, 3)));
+        let (i, j) = q.find_sorted("فِيهِ ۛ هُدًى")[0];
+        assert_eq!(q.get_word_location((i, j + "فِيهِ ۛ ".len())), Some((2, 2, 6)));
+        assert_eq!(q.get_word_location((0, 1000)), None);
+        let (i, j) = q.get_word_index((1, 1, 4)).unwrap();
+        assert_eq!(q.get_word_location((i, j + 4)), Some((1, 1, 4)));
+        assert_eq!(q.get_word_location((i, j - 1)), Some((1, 1, 3)));
+        assert_eq!(
+            q.get_word_location((i, q.get_data(i).unwrap().3.len())),
+            Some((1, 1, 4))
+        );
+
+        let q = Quranize::with_basmalah(true);
+        let (i, j) = q.find_sorted("الم")[0];
+        assert_eq!(i, 7);
+        assert_eq!(q.get_word_location((i, j)), Some((2, 1, 1)));
+        assert_eq!(q.get_word_location((i, 0)), None);
+        assert_eq!(q.get_word_index((2, 1, 1)), Some((i, j)));
+
+        let q = Quranize::new();
+        for location in [(2, 2, 1), (2, 2, 5), (2, 7, 10), (114, 6, 3)] {
+            let index = q.get_word_index(location).unwrap();
+            assert_eq!(q.get_word_location(index), Some(location));
+        }
+        let (i, j) = q.get_word_index((2, 2, 7)).unwrap();
+        assert_eq!(&q.get_data(i).unwrap().3[j..], "لِّلْمُتَّقِينَ");
+        assert_eq!(q.get_word_index((2, 2, 10)), None);
+        assert_eq!(q.get_word_index((0, 1, 1)), None);
+        assert_eq!(q.get_word_index((115, 1, 1)), None);
+    }
+
+    #[test]
+    fn test_uthmani_word_numbers() {
+        let q = Quranize::new();
+        let text = |(i, j): Index| &q.get_data(i).unwrap().3[j..];
+        let (i, j) = q.get_word_index((2, 21, 1)).unwrap();
+        assert!(text((i, j)).starts_with("يَا أَيُّهَا النَّاسُ"));
+        assert_eq!(q.get_word_location((i, j + "يَا ".len())), Some((2, 21, 1)));
+        assert_eq!(
+            q.get_word_location((i, j + "يَا أَيُّهَا ".len())),
+            Some((2, 21, 2))
+        );
+        assert_eq!(
+            q.get_word_range((2, 21, 1)),
+            Some((i, j..j + "يَا أَيُّهَا".len()))
+        );
+        assert!(text(q.get_word_index((2, 21, 2)).unwrap()).starts_with("النَّاسُ"));
+        assert!(text(q.get_word_index((20, 94, 3)).unwrap()).starts_with("لَا تَأْخُذْ"));
+        assert!(text(q.get_word_index((3, 66, 2)).unwrap()).starts_with("هَٰؤُلَاءِ"));
+        assert!(text(q.get_word_index((7, 150, 24)).unwrap()).starts_with("ابْنَ أُمَّ"));
+        let word_count = |i| {
+            numbered_words(q.get_data(i).unwrap().3, 0)
+                .last()
+                .unwrap()
+                .1
+        };
+        assert_eq!(word_count(aya_row(32, 15).unwrap()), 15);
+    }
+}